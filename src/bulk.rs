@@ -0,0 +1,652 @@
+//! # Bulk
+//!
+//! Create, update, upsert and delete many documents with a bounded number of
+//! requests in flight. Each run returns a [`BulkReport`] with the outcome of
+//! every item, so one bad document does not abort the others.
+//!
+//! With [`BulkOptions::batch_size`] set, documents are sent in batches to
+//! Appwrite's bulk endpoints. A server without them answers with a missing
+//! route, which switches the rest of the run to one request per document.
+//! Any other rejected batch, e.g. one over the server's size cap, is retried
+//! one document at a time to find the failing items. Documents of a bulk
+//! response are matched to the input by `$id`, and IDs missing from a bulk
+//! delete are reported as not found.
+//! ```no_run
+//! use futures_util::stream;
+//! use serde_json::json;
+//! use unofficial_appwrite::{
+//!     bulk::{BulkDocument, BulkOptions},
+//!     client::ClientBuilder,
+//!     services::server::databases::Databases,
+//! };
+//!
+//! # async fn run() -> Result<(), unofficial_appwrite::error::Error> {
+//! let client = ClientBuilder::from_env()?.build()?;
+//! let books = (0..10_000).map(|i| BulkDocument::new(format!("book-{}", i), json!({"title": i})));
+//! let options = BulkOptions {
+//!     batch_size: Some(100),
+//!     ..Default::default()
+//! };
+//! let report =
+//!     Databases::bulk_create_documents(&client, "db", "books", stream::iter(books), &options).await;
+//! for failure in report.failures() {
+//!     eprintln!("{}: {:?}", failure.id, failure.result);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use std::{
+    collections::{HashMap, HashSet},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use futures_util::{stream, Stream, StreamExt};
+use serde_json::{json, Value};
+
+use crate::{
+    client::Client, enums::appwrite_error_kind::AppwriteErrorKind, error::Error,
+    models::document::Document, query::Query, services::server::databases::Databases,
+};
+
+/// Settings of the `bulk_*_documents` helpers.
+#[derive(Debug, Clone)]
+pub struct BulkOptions {
+    /// Maximum number of requests in flight.
+    pub concurrency: usize,
+    /// Documents per request to the bulk endpoints, `None` for one request
+    /// per document. Updates are always sent one by one.
+    pub batch_size: Option<usize>,
+}
+
+impl Default for BulkOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: 8,
+            batch_size: None,
+        }
+    }
+}
+
+/// Payload of one document.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BulkDocument {
+    /// Document ID, `unique()` to let Appwrite pick one on create.
+    pub id: String,
+    pub data: Value,
+    pub permissions: Option<Vec<String>>,
+}
+
+impl BulkDocument {
+    pub fn new(id: impl Into<String>, data: Value) -> Self {
+        Self {
+            id: id.into(),
+            data,
+            permissions: None,
+        }
+    }
+
+    pub fn permissions(mut self, permissions: Vec<String>) -> Self {
+        self.permissions = Some(permissions);
+        self
+    }
+
+    /// Document as sent in the `documents` of a bulk request.
+    fn to_value(&self) -> Value {
+        let mut value = self.data.clone();
+        if let Value::Object(map) = &mut value {
+            map.insert("$id".to_string(), json!(self.id));
+            if let Some(permissions) = &self.permissions {
+                map.insert("$permissions".to_string(), json!(permissions));
+            }
+        }
+        value
+    }
+}
+
+/// Outcome of one item of a bulk run.
+#[derive(Debug)]
+pub struct BulkItem<T> {
+    /// Position of the item in the input.
+    pub index: usize,
+    /// Document ID of the item.
+    pub id: String,
+    pub result: Result<T, Error>,
+}
+
+/// Outcome of every item of a bulk run, in input order.
+#[derive(Debug)]
+pub struct BulkReport<T> {
+    pub items: Vec<BulkItem<T>>,
+}
+
+impl<T> BulkReport<T> {
+    pub fn successes(&self) -> impl Iterator<Item = &BulkItem<T>> {
+        self.items.iter().filter(|item| item.result.is_ok())
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &BulkItem<T>> {
+        self.items.iter().filter(|item| item.result.is_err())
+    }
+
+    /// Whether every item succeeded.
+    pub fn is_success(&self) -> bool {
+        self.items.iter().all(|item| item.result.is_ok())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operation {
+    Create,
+    Update,
+    Upsert,
+    Delete,
+}
+
+impl Databases {
+    /// Bulk create documents
+    ///
+    /// Create every document of `documents`, see [`bulk`](crate::bulk).
+    pub async fn bulk_create_documents(
+        client: &Client,
+        database_id: &str,
+        collection_id: &str,
+        documents: impl Stream<Item = BulkDocument>,
+        options: &BulkOptions,
+    ) -> BulkReport<Document> {
+        let target = Target::new(client, database_id, collection_id, Operation::Create);
+        documents_report(target.run(documents, options).await)
+    }
+
+    /// Bulk update documents
+    ///
+    /// Update the data and permissions of every document of `documents`, one
+    /// request per document.
+    pub async fn bulk_update_documents(
+        client: &Client,
+        database_id: &str,
+        collection_id: &str,
+        documents: impl Stream<Item = BulkDocument>,
+        options: &BulkOptions,
+    ) -> BulkReport<Document> {
+        let target = Target::new(client, database_id, collection_id, Operation::Update);
+        documents_report(target.run(documents, options).await)
+    }
+
+    /// Bulk upsert documents
+    ///
+    /// Create or replace every document of `documents`.
+    pub async fn bulk_upsert_documents(
+        client: &Client,
+        database_id: &str,
+        collection_id: &str,
+        documents: impl Stream<Item = BulkDocument>,
+        options: &BulkOptions,
+    ) -> BulkReport<Document> {
+        let target = Target::new(client, database_id, collection_id, Operation::Upsert);
+        documents_report(target.run(documents, options).await)
+    }
+
+    /// Bulk delete documents
+    ///
+    /// Delete every document of `document_ids`.
+    pub async fn bulk_delete_documents(
+        client: &Client,
+        database_id: &str,
+        collection_id: &str,
+        document_ids: impl Stream<Item = String>,
+        options: &BulkOptions,
+    ) -> BulkReport<()> {
+        let target = Target::new(client, database_id, collection_id, Operation::Delete);
+        let documents = document_ids.map(|id| BulkDocument::new(id, Value::Null));
+        let items = target.run(documents, options).await;
+        BulkReport {
+            items: items
+                .into_iter()
+                .map(|item| BulkItem {
+                    index: item.index,
+                    id: item.id,
+                    result: item.result.map(|_| ()),
+                })
+                .collect(),
+        }
+    }
+}
+
+fn documents_report(items: Vec<BulkItem<Option<Document>>>) -> BulkReport<Document> {
+    BulkReport {
+        items: items
+            .into_iter()
+            .map(|item| BulkItem {
+                index: item.index,
+                id: item.id,
+                result: item.result.and_then(|document| {
+                    document.ok_or_else(|| {
+                        Error::Custom("document missing from the bulk response".to_string())
+                    })
+                }),
+            })
+            .collect(),
+    }
+}
+
+/// Collection a bulk run writes to.
+struct Target<'a> {
+    client: &'a Client,
+    database_id: &'a str,
+    collection_id: &'a str,
+    operation: Operation,
+    /// Cleared once the server answered that it has no bulk endpoint.
+    native: AtomicBool,
+}
+
+impl<'a> Target<'a> {
+    fn new(
+        client: &'a Client,
+        database_id: &'a str,
+        collection_id: &'a str,
+        operation: Operation,
+    ) -> Self {
+        Self {
+            client,
+            database_id,
+            collection_id,
+            operation,
+            native: AtomicBool::new(operation != Operation::Update),
+        }
+    }
+
+    async fn run(
+        &self,
+        documents: impl Stream<Item = BulkDocument>,
+        options: &BulkOptions,
+    ) -> Vec<BulkItem<Option<Document>>> {
+        let batch_size = options.batch_size.unwrap_or(1).max(1);
+        let native = options.batch_size.is_some();
+        let mut items: Vec<_> = documents
+            .enumerate()
+            .chunks(batch_size)
+            .map(|chunk| self.chunk(chunk, native))
+            .buffer_unordered(options.concurrency.max(1))
+            .flat_map(stream::iter)
+            .collect()
+            .await;
+        items.sort_by_key(|item| item.index);
+        items
+    }
+
+    /// Send `chunk` as one bulk request when `native`, falling back to one
+    /// request per document.
+    async fn chunk(
+        &self,
+        chunk: Vec<(usize, BulkDocument)>,
+        native: bool,
+    ) -> Vec<BulkItem<Option<Document>>> {
+        if native && self.native.load(Ordering::Relaxed) {
+            let documents: Vec<_> = chunk.iter().map(|(_, document)| document).collect();
+            match self.batch(&documents).await {
+                Ok(results) => {
+                    return chunk
+                        .into_iter()
+                        .zip(results)
+                        .map(|((index, document), result)| BulkItem {
+                            index,
+                            id: document.id,
+                            result,
+                        })
+                        .collect();
+                }
+                Err(err) if err.kind() == Some(AppwriteErrorKind::GeneralRouteNotFound) => {
+                    self.native.store(false, Ordering::Relaxed);
+                }
+                // e.g. a batch over the server's size cap: only this chunk
+                // goes one by one
+                Err(_) => {}
+            }
+        }
+
+        let mut items = Vec::with_capacity(chunk.len());
+        for (index, document) in chunk {
+            let result = self.one(&document).await;
+            items.push(BulkItem {
+                index,
+                id: document.id,
+                result,
+            });
+        }
+        items
+    }
+
+    async fn one(&self, document: &BulkDocument) -> Result<Option<Document>, Error> {
+        let (client, db, col) = (self.client, self.database_id, self.collection_id);
+        let mut args = HashMap::new();
+        if self.operation != Operation::Delete {
+            args.insert("data".to_string(), document.data.clone());
+        }
+        if let Some(permissions) = &document.permissions {
+            args.insert("permissions".to_string(), json!(permissions));
+        }
+        match self.operation {
+            Operation::Create => {
+                args.insert("documentId".to_string(), json!(document.id));
+                Databases::create_documents(client, db, col, args)
+                    .await
+                    .map(Some)
+            }
+            Operation::Update => Databases::update_document(client, db, col, &document.id, args)
+                .await
+                .map(Some),
+            Operation::Upsert => Databases::upsert_document(client, db, col, &document.id, args)
+                .await
+                .map(Some),
+            Operation::Delete => Databases::delete_document(client, db, col, &document.id)
+                .await
+                .map(|_| None),
+        }
+    }
+
+    /// Send `documents` as one bulk request, with the result of each
+    /// document in input order.
+    async fn batch(
+        &self,
+        documents: &[&BulkDocument],
+    ) -> Result<Vec<Result<Option<Document>, Error>>, Error> {
+        let (client, db, col) = (self.client, self.database_id, self.collection_id);
+        let mut args = HashMap::new();
+        match self.operation {
+            Operation::Create | Operation::Upsert => {
+                let values: Vec<_> = documents.iter().map(|d| d.to_value()).collect();
+                args.insert("documents".to_string(), json!(values));
+                let list = if self.operation == Operation::Create {
+                    Databases::create_many_documents(client, db, col, args).await?
+                } else {
+                    Databases::upsert_documents(client, db, col, args).await?
+                };
+                Ok(match_documents(documents, list.documents)
+                    .into_iter()
+                    .map(Ok)
+                    .collect())
+            }
+            Operation::Delete => {
+                let ids: Vec<_> = documents.iter().map(|d| d.id.as_str()).collect();
+                args.insert(
+                    "queries".to_string(),
+                    json!([
+                        Query::equal("$id".into(), ids.into()),
+                        Query::limit(documents.len().into())
+                    ]),
+                );
+                let list = Databases::delete_documents(client, db, col, args).await?;
+                Ok(match_documents(documents, list.documents)
+                    .into_iter()
+                    .map(|deleted| deleted.map(|_| None).ok_or_else(not_found))
+                    .collect())
+            }
+            Operation::Update => unreachable!("updates are sent one by one"),
+        }
+    }
+}
+
+/// Pair the documents of a bulk response with the input: by `$id` for
+/// input with an ID, in response order for `unique()` ones.
+fn match_documents(input: &[&BulkDocument], response: Vec<Document>) -> Vec<Option<Document>> {
+    let mut by_id: HashMap<String, Document> = HashMap::new();
+    let mut unnamed = Vec::new();
+    let named: HashSet<_> = input
+        .iter()
+        .filter(|document| document.id != "unique()")
+        .map(|document| document.id.as_str())
+        .collect();
+    for document in response {
+        if named.contains(document.id.as_str()) {
+            by_id.insert(document.id.clone(), document);
+        } else {
+            unnamed.push(document);
+        }
+    }
+    let mut unnamed = unnamed.into_iter();
+    input
+        .iter()
+        .map(|document| {
+            if document.id == "unique()" {
+                unnamed.next()
+            } else {
+                by_id.remove(&document.id)
+            }
+        })
+        .collect()
+}
+
+/// Error of a document which a bulk delete did not find.
+fn not_found() -> Error {
+    Error::AppWriteError {
+        message: "Document with the requested ID could not be found.".to_string(),
+        code: Some(404),
+        response: None,
+        error_type: Some("document_not_found".to_string()),
+        status: 404,
+        version: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        enumm::HttpMethod,
+        transport::{MockResponse, MockTransport},
+    };
+
+    fn document(id: &str) -> Value {
+        json!({"$id": id, "$collectionId": "books", "$databaseId": "db",
+               "$createdAt": "", "$updatedAt": "", "$permissions": []})
+    }
+
+    fn error(status: u16, error_type: &str) -> MockResponse {
+        MockResponse::json(
+            status,
+            json!({"message": error_type, "code": status, "type": error_type}),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_bulk_create_isolates_failures() -> Result<(), Error> {
+        let (mock, client) = MockTransport::client()?;
+        let path = "/databases/db/collections/books/documents";
+        mock.on(
+            HttpMethod::POST,
+            path,
+            error(400, "document_invalid_structure"),
+        )
+        .on(
+            HttpMethod::POST,
+            path,
+            MockResponse::json(201, document("a")),
+        )
+        .on(
+            HttpMethod::POST,
+            path,
+            error(400, "document_invalid_structure"),
+        )
+        .on(
+            HttpMethod::POST,
+            path,
+            MockResponse::json(201, json!({"total": 1, "documents": [document("c")]})),
+        );
+
+        let documents = ["a", "b", "c"].map(|id| BulkDocument::new(id, json!({"title": id})));
+        let options = BulkOptions {
+            concurrency: 1,
+            batch_size: Some(2),
+        };
+        let report = Databases::bulk_create_documents(
+            &client,
+            "db",
+            "books",
+            stream::iter(documents),
+            &options,
+        )
+        .await;
+
+        assert!(!report.is_success());
+        let ids: Vec<_> = report.successes().map(|item| item.id.as_str()).collect();
+        assert_eq!(ids, ["a", "c"]);
+        let failure = report.failures().next().unwrap();
+        assert_eq!((failure.index, failure.id.as_str()), (1, "b"));
+        assert!(failure.result.as_ref().unwrap_err().is_validation());
+
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 4);
+        assert_eq!(
+            requests[0].body.as_ref().unwrap()["documents"][1],
+            json!({"$id": "b", "title": "b"})
+        );
+        assert_eq!(requests[1].body.as_ref().unwrap()["documentId"], json!("a"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_bulk_delete_without_bulk_endpoints() -> Result<(), Error> {
+        let (mock, client) = MockTransport::client()?;
+        let path = "/databases/db/collections/books/documents";
+        mock.on(
+            HttpMethod::DELETE,
+            path,
+            error(404, "general_route_not_found"),
+        )
+        .on(
+            HttpMethod::DELETE,
+            &format!("{}/x", path),
+            MockResponse::bytes(204, ""),
+        )
+        .on(
+            HttpMethod::DELETE,
+            &format!("{}/y", path),
+            error(404, "document_not_found"),
+        )
+        .on(
+            HttpMethod::DELETE,
+            &format!("{}/z", path),
+            MockResponse::bytes(204, ""),
+        );
+
+        let ids = ["x", "y", "z"].map(String::from);
+        let options = BulkOptions {
+            concurrency: 1,
+            batch_size: Some(2),
+        };
+        let report =
+            Databases::bulk_delete_documents(&client, "db", "books", stream::iter(ids), &options)
+                .await;
+
+        let failed: Vec<_> = report.failures().map(|item| item.id.as_str()).collect();
+        assert_eq!(failed, ["y"]);
+        assert_eq!(report.items.len(), 3);
+        let bulk_requests = mock
+            .requests()
+            .iter()
+            .filter(|request| request.path == path)
+            .count();
+        assert_eq!(bulk_requests, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_bulk_create_retries_rejected_chunk_one_by_one() -> Result<(), Error> {
+        let (mock, client) = MockTransport::client()?;
+        let path = "/databases/db/collections/books/documents";
+        mock.on(
+            HttpMethod::POST,
+            path,
+            error(400, "general_argument_invalid"),
+        )
+        .on(
+            HttpMethod::POST,
+            path,
+            MockResponse::json(201, document("a")),
+        )
+        .on(
+            HttpMethod::POST,
+            path,
+            MockResponse::json(201, document("b")),
+        )
+        .on(
+            HttpMethod::POST,
+            path,
+            MockResponse::json(
+                201,
+                json!({"total": 2, "documents": [document("c"), document("d")]}),
+            ),
+        );
+
+        let documents = ["a", "b", "c", "d"].map(|id| BulkDocument::new(id, json!({})));
+        let options = BulkOptions {
+            concurrency: 1,
+            batch_size: Some(2),
+        };
+        let report = Databases::bulk_create_documents(
+            &client,
+            "db",
+            "books",
+            stream::iter(documents),
+            &options,
+        )
+        .await;
+
+        assert!(report.is_success());
+        assert_eq!(report.items.len(), 4);
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 4);
+        let bulk_requests = requests
+            .iter()
+            .filter(|request| request.body.as_ref().unwrap().get("documents").is_some())
+            .count();
+        assert_eq!(bulk_requests, 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_bulk_results_are_matched_by_id() -> Result<(), Error> {
+        let (mock, client) = MockTransport::client()?;
+        let path = "/databases/db/collections/books/documents";
+        mock.on(
+            HttpMethod::PUT,
+            path,
+            MockResponse::json(
+                200,
+                json!({"total": 2, "documents": [document("b"), document("a")]}),
+            ),
+        )
+        .on(
+            HttpMethod::DELETE,
+            path,
+            MockResponse::json(200, json!({"total": 1, "documents": [document("z")]})),
+        );
+
+        let documents = ["a", "b"].map(|id| BulkDocument::new(id, json!({})));
+        let options = BulkOptions {
+            concurrency: 1,
+            batch_size: Some(2),
+        };
+        let report = Databases::bulk_upsert_documents(
+            &client,
+            "db",
+            "books",
+            stream::iter(documents),
+            &options,
+        )
+        .await;
+        for item in &report.items {
+            assert_eq!(item.result.as_ref().unwrap().id, item.id);
+        }
+
+        let ids = ["y", "z"].map(String::from);
+        let report =
+            Databases::bulk_delete_documents(&client, "db", "books", stream::iter(ids), &options)
+                .await;
+        let failure = report.failures().next().unwrap();
+        assert_eq!(failure.id, "y");
+        assert!(failure.result.as_ref().unwrap_err().is_not_found());
+        assert_eq!(report.successes().count(), 1);
+        Ok(())
+    }
+}
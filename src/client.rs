@@ -1,4 +1,4 @@
-use std::{collections::HashMap, fs, str::FromStr};
+use std::{collections::HashMap, fs, future::Future, str::FromStr, sync::Arc, time::Duration};
 
 use reqwest::{
     header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE},
@@ -6,15 +6,28 @@ use reqwest::{
     Response, StatusCode,
 };
 use serde_json::{json, Value};
+use tokio::net::TcpStream;
+use tokio_tungstenite::{
+    client_async_with_config, connect_async_tls_with_config, tungstenite, Connector,
+    MaybeTlsStream, WebSocketStream,
+};
 use uuid::Uuid;
 
 use crate::{
     enumm::HttpMethod,
-    error::{AppWriteError, Error},
+    error::{truncate_body, AppWriteError, Error},
+    interceptor::{Interceptor, RequestFlow, RequestParts},
     models::{deployment::Deployment, file::File, UploadType},
+    pinning::{self, Fingerprint, PinLayer, PinMismatch},
+    rate_limit::{RateLimiter, RateLimiterConfig},
+    request_options::RequestOptions,
+    response_meta::{MetaCapture, MetaError, WithMeta},
+    retry::RetryPolicy,
+    transport::{HttpTransport, RequestBody, ReqwestTransport, TransportRequest},
+    utils::encode_query,
 };
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct ChunkProgress {
     pub chunks_uploaded: u64,
     pub chunks_total: u64,
@@ -34,13 +47,21 @@ impl ChunkProgress {
     }
 }
 
+/// Appwrite client.
+///
+/// Holds a single pooled HTTP transport which is shared by every clone of the
+/// client, so connections, TLS sessions and keep-alive are reused across calls.
 #[derive(Debug, Clone)]
 pub struct Client {
     end_point: String,
     pub end_point_realtime: Option<String>, //todo set this
     pub header: HeaderMap,
     chunk_size: usize,
-    _self_signed: bool,
+    tls: TlsConfig,
+    retry: Option<RetryPolicy>,
+    transport: Arc<dyn HttpTransport>,
+    interceptors: Vec<Arc<dyn Interceptor>>,
+    rate_limiter: Option<Arc<RateLimiter>>,
 }
 
 #[derive(Clone)]
@@ -49,7 +70,191 @@ pub struct ClientBuilder {
     pub end_point_realtime: Option<String>, //todo set this
     pub header: HeaderMap,
     chunk_size: Option<usize>,
-    self_signed: Option<bool>,
+    transport: TransportConfig,
+    tls: TlsConfig,
+    retry: Option<RetryPolicy>,
+    custom_transport: Option<Arc<dyn HttpTransport>>,
+    interceptors: Vec<Arc<dyn Interceptor>>,
+    rate_limit: Option<RateLimiterConfig>,
+}
+
+/// Connection settings used to build the pooled HTTP transport of a [`Client`].
+#[derive(Debug, Clone, Default)]
+pub struct TransportConfig {
+    /// Timeout for establishing a connection.
+    pub connect_timeout: Option<Duration>,
+    /// Timeout for each read operation on an open connection.
+    pub read_timeout: Option<Duration>,
+    /// Timeout for the whole request, from connecting to reading the body.
+    pub timeout: Option<Duration>,
+    /// Maximum number of idle connections kept per host.
+    pub pool_max_idle_per_host: Option<usize>,
+    /// How long an idle connection is kept in the pool.
+    pub pool_idle_timeout: Option<Duration>,
+    /// TCP keep-alive interval.
+    pub tcp_keepalive: Option<Duration>,
+    /// Offer HTTP/2 during the TLS handshake (ALPN) and fall back to HTTP/1.1
+    /// when the server or proxy does not accept it. Off means HTTP/1.1 only.
+    pub http2_preferred: bool,
+    /// Force HTTP/2 without negotiating (prior knowledge). Requests fail
+    /// against HTTP/1.1-only servers and proxies; takes precedence over
+    /// [`TransportConfig::http2_preferred`].
+    pub http2_prior_knowledge: bool,
+    /// Proxy url used for every request, e.g. `http://127.0.0.1:8080`.
+    pub proxy: Option<String>,
+}
+
+/// TLS settings shared by the REST transport and realtime websocket connections.
+#[derive(Debug, Clone, Default)]
+pub struct TlsConfig {
+    /// Accept invalid and self-signed certificates. Use only on dev mode.
+    pub self_signed: bool,
+    /// Extra PEM encoded CA certificates to trust.
+    pub ca_certificates: Vec<Vec<u8>>,
+    /// Trust only [`TlsConfig::ca_certificates`] and ignore the system roots.
+    pub roots_only: bool,
+    /// PEM encoded client certificate chain and PKCS #8 private key for mutual TLS.
+    pub identity: Option<(Vec<u8>, Vec<u8>)>,
+    /// SHA-256 fingerprints of the accepted server leaf certificates, see
+    /// [`pinning`](crate::pinning). Empty to accept any valid certificate.
+    pub pinned_certificates: Vec<Fingerprint>,
+}
+
+impl TlsConfig {
+    fn apply(&self, mut builder: reqwest::ClientBuilder) -> Result<reqwest::ClientBuilder, Error> {
+        builder = builder.danger_accept_invalid_certs(self.self_signed);
+        for pem in &self.ca_certificates {
+            for cert in reqwest::Certificate::from_pem_bundle(pem)? {
+                builder = builder.add_root_certificate(cert);
+            }
+        }
+        if self.roots_only {
+            builder = builder.tls_built_in_root_certs(false);
+        }
+        if let Some((cert, key)) = self.identity.as_ref() {
+            builder = builder.identity(reqwest::Identity::from_pkcs8_pem(cert, key)?);
+        }
+        if !self.pinned_certificates.is_empty() {
+            builder = builder
+                .tls_info(true)
+                .connector_layer(PinLayer::new(&self.pinned_certificates));
+        }
+        Ok(builder)
+    }
+
+    /// Websocket connector honouring these settings, `None` when the defaults apply.
+    fn websocket_connector(&self) -> Result<Option<Connector>, Error> {
+        if !self.self_signed
+            && self.ca_certificates.is_empty()
+            && !self.roots_only
+            && self.identity.is_none()
+        {
+            return Ok(None);
+        }
+        Ok(Some(Connector::NativeTls(self.native_connector()?)))
+    }
+
+    fn native_connector(&self) -> Result<native_tls::TlsConnector, Error> {
+        let mut builder = native_tls::TlsConnector::builder();
+        builder
+            .danger_accept_invalid_certs(self.self_signed)
+            .disable_built_in_roots(self.roots_only);
+        for pem in &self.ca_certificates {
+            for cert in split_pem_bundle(pem) {
+                builder.add_root_certificate(native_tls::Certificate::from_pem(&cert)?);
+            }
+        }
+        if let Some((cert, key)) = self.identity.as_ref() {
+            builder.identity(native_tls::Identity::from_pkcs8(cert, key)?);
+        }
+        Ok(builder.build()?)
+    }
+
+    /// Open a websocket to `url`, checking the pinned certificates when set.
+    pub(crate) async fn connect_websocket(
+        &self,
+        url: &str,
+    ) -> Result<WebSocketStream<MaybeTlsStream<TcpStream>>, Error> {
+        let connect_error =
+            |err: tungstenite::Error| Error::Custom(format!("Can't connect: {}", err));
+        if self.pinned_certificates.is_empty() {
+            let connector = self.websocket_connector()?;
+            let (stream, _response) = connect_async_tls_with_config(url, None, false, connector)
+                .await
+                .map_err(connect_error)?;
+            return Ok(stream);
+        }
+
+        let parsed = url::Url::parse(url).map_err(|err| Error::InvalidSetting {
+            setting: "realtime endpoint".to_string(),
+            message: err.to_string(),
+        })?;
+        let (Some(host), Some(port), "wss") = (
+            parsed.host_str(),
+            parsed.port_or_known_default(),
+            parsed.scheme(),
+        ) else {
+            return Err(Error::Tls(Box::new(PinMismatch { fingerprint: None })));
+        };
+        let tcp = TcpStream::connect((host, port)).await?;
+        let tls = tokio_native_tls::TlsConnector::from(self.native_connector()?)
+            .connect(host, tcp)
+            .await?;
+        let der = tls
+            .get_ref()
+            .peer_certificate()?
+            .map(|cert| cert.to_der())
+            .transpose()?;
+        pinning::check(&self.pinned_certificates, der.as_deref())
+            .map_err(|mismatch| Error::Tls(Box::new(mismatch)))?;
+        let (stream, _response) =
+            client_async_with_config(url, MaybeTlsStream::NativeTls(tls), None)
+                .await
+                .map_err(connect_error)?;
+        Ok(stream)
+    }
+}
+
+fn split_pem_bundle(pem: &[u8]) -> Vec<Vec<u8>> {
+    const END: &str = "-----END CERTIFICATE-----";
+    String::from_utf8_lossy(pem)
+        .split_inclusive(END)
+        .filter(|block| block.contains(END))
+        .map(|block| block.trim_start().as_bytes().to_vec())
+        .collect()
+}
+
+impl TransportConfig {
+    fn build(&self, tls: &TlsConfig) -> Result<reqwest::Client, Error> {
+        let mut builder = tls.apply(reqwest::Client::builder())?;
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.read_timeout {
+            builder = builder.read_timeout(timeout);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(max) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        if let Some(timeout) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        if let Some(interval) = self.tcp_keepalive {
+            builder = builder.tcp_keepalive(interval);
+        }
+        if self.http2_prior_knowledge {
+            builder = builder.http2_prior_knowledge();
+        } else if !self.http2_preferred {
+            builder = builder.http1_only();
+        }
+        if let Some(proxy) = self.proxy.as_ref() {
+            builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+        }
+        Ok(builder.build()?)
+    }
 }
 
 impl Default for ClientBuilder {
@@ -59,7 +264,12 @@ impl Default for ClientBuilder {
             end_point_realtime: Some(String::from("wss://cloud.appwrite.io/v1")),
             header: HeaderMap::new(),
             chunk_size: Some(5 * 1024 * 1024),
-            self_signed: Some(false),
+            transport: TransportConfig::default(),
+            tls: TlsConfig::default(),
+            retry: None,
+            custom_transport: None,
+            interceptors: Vec::new(),
+            rate_limit: None,
         }
     }
 }
@@ -70,19 +280,92 @@ impl ClientBuilder {
             ..Default::default()
         }
     }
+    /// Accept self-signed certificates on both REST and realtime connections.
+    /// Use only on dev mode.
     pub fn set_self_signed(&mut self, status: bool) -> Result<&mut Self, Error> {
-        self.self_signed = Some(status);
+        self.tls.self_signed = status;
+        Ok(self)
+    }
+
+    /// Trust the CA certificate(s) in a PEM bundle in addition to the system roots.
+    pub fn add_ca_certificate_pem(&mut self, pem: &[u8]) -> Result<&mut Self, Error> {
+        if reqwest::Certificate::from_pem_bundle(pem)?.is_empty() {
+            return Err(Error::Custom(
+                "no certificate found in the PEM bundle".to_string(),
+            ));
+        }
+        self.tls.ca_certificates.push(pem.to_vec());
+        Ok(self)
+    }
+
+    /// Same as [`ClientBuilder::add_ca_certificate_pem`] but reads the bundle from a file.
+    pub fn add_ca_certificate_file(&mut self, path: &str) -> Result<&mut Self, Error> {
+        let pem = fs::read(path)?;
+        self.add_ca_certificate_pem(&pem)
+    }
+
+    /// Trust the CA certificate(s) in a PEM bundle and ignore the system roots.
+    ///
+    /// Any server certificate chaining to one of the given CAs (or to another
+    /// added CA certificate) is accepted; combine with
+    /// [`ClientBuilder::add_pinned_certificate_pem`] to accept only one
+    /// certificate.
+    pub fn set_root_certificate_only(&mut self, pem: &[u8]) -> Result<&mut Self, Error> {
+        self.add_ca_certificate_pem(pem)?;
+        self.tls.roots_only = true;
+        Ok(self)
+    }
+
+    /// Pin the server certificate by the SHA-256 fingerprint of its DER
+    /// encoding, as hex with or without `:` separators. Once a pin is set, REST
+    /// and realtime connections to a server whose leaf certificate is not
+    /// pinned fail with [`Error::Tls`], see [`pinning`](crate::pinning).
+    pub fn add_pinned_certificate_sha256(&mut self, fingerprint: &str) -> Result<&mut Self, Error> {
+        let fingerprint =
+            pinning::parse_fingerprint(fingerprint).ok_or_else(|| Error::InvalidSetting {
+                setting: "pinned certificate".to_string(),
+                message: format!("`{}` is not a hex SHA-256 fingerprint", fingerprint),
+            })?;
+        self.tls.pinned_certificates.push(fingerprint);
+        Ok(self)
+    }
+
+    /// Pin every certificate of a PEM bundle, see
+    /// [`ClientBuilder::add_pinned_certificate_sha256`]. The certificates are
+    /// pinned, not trusted: a self-signed one also needs
+    /// [`ClientBuilder::add_ca_certificate_pem`].
+    pub fn add_pinned_certificate_pem(&mut self, pem: &[u8]) -> Result<&mut Self, Error> {
+        let certs = split_pem_bundle(pem);
+        if certs.is_empty() {
+            return Err(Error::Custom(
+                "no certificate found in the PEM bundle".to_string(),
+            ));
+        }
+        for cert in certs {
+            let der = native_tls::Certificate::from_pem(&cert)?.to_der()?;
+            self.tls
+                .pinned_certificates
+                .push(pinning::fingerprint(&der));
+        }
+        Ok(self)
+    }
+
+    /// Present a client certificate for mutual TLS.
+    ///
+    ///* cert => PEM encoded certificate chain
+    ///* key => PEM encoded PKCS #8 private key
+    pub fn set_client_identity_pem(&mut self, cert: &[u8], key: &[u8]) -> Result<&mut Self, Error> {
+        reqwest::Identity::from_pkcs8_pem(cert, key)?;
+        self.tls.identity = Some((cert.to_vec(), key.to_vec()));
         Ok(self)
     }
     pub fn set_endpoint(&mut self, endpoint: &str) -> Result<&mut Self, Error> {
         self.end_point = Some(String::from(endpoint));
         if self.end_point_realtime.as_ref().is_none() {
-            self.end_point_realtime = self.end_point.clone().and_then(|value| {
-                Some(
-                    value
-                        .replace("https://", "wss://")
-                        .replace("http://", "ws://"),
-                )
+            self.end_point_realtime = self.end_point.clone().map(|value| {
+                value
+                    .replace("https://", "wss://")
+                    .replace("http://", "ws://")
             });
         }
         Ok(self)
@@ -117,21 +400,209 @@ impl ClientBuilder {
         Ok(self)
     }
 
+    pub fn set_connect_timeout(&mut self, timeout: Duration) -> Result<&mut Self, Error> {
+        self.transport.connect_timeout = Some(timeout);
+        Ok(self)
+    }
+
+    pub fn set_read_timeout(&mut self, timeout: Duration) -> Result<&mut Self, Error> {
+        self.transport.read_timeout = Some(timeout);
+        Ok(self)
+    }
+
+    pub fn set_timeout(&mut self, timeout: Duration) -> Result<&mut Self, Error> {
+        self.transport.timeout = Some(timeout);
+        Ok(self)
+    }
+
+    pub fn set_pool_max_idle_per_host(&mut self, max: usize) -> Result<&mut Self, Error> {
+        self.transport.pool_max_idle_per_host = Some(max);
+        Ok(self)
+    }
+
+    pub fn set_pool_idle_timeout(&mut self, timeout: Duration) -> Result<&mut Self, Error> {
+        self.transport.pool_idle_timeout = Some(timeout);
+        Ok(self)
+    }
+
+    pub fn set_tcp_keepalive(&mut self, interval: Duration) -> Result<&mut Self, Error> {
+        self.transport.tcp_keepalive = Some(interval);
+        Ok(self)
+    }
+
+    /// Prefer HTTP/2 on TLS connections, negotiated with ALPN and falling back
+    /// to HTTP/1.1. Plain `http://` endpoints keep using HTTP/1.1.
+    pub fn set_http2_preferred(&mut self, status: bool) -> Result<&mut Self, Error> {
+        self.transport.http2_preferred = status;
+        Ok(self)
+    }
+
+    /// Force HTTP/2 without negotiation (prior knowledge). Only for servers
+    /// known to speak HTTP/2, it breaks HTTP/1.1-only servers and proxies; see
+    /// [`ClientBuilder::set_http2_preferred`] for the negotiated variant.
+    pub fn set_http2_prior_knowledge(&mut self, status: bool) -> Result<&mut Self, Error> {
+        self.transport.http2_prior_knowledge = status;
+        Ok(self)
+    }
+
+    pub fn set_proxy(&mut self, url: &str) -> Result<&mut Self, Error> {
+        reqwest::Proxy::all(url)?;
+        self.transport.proxy = Some(String::from(url));
+        Ok(self)
+    }
+
+    /// Retry transient failures, see [`RetryPolicy`].
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) -> Result<&mut Self, Error> {
+        if policy.max_attempts == 0 {
+            return Err(Error::Custom(
+                "retry policy needs at least one attempt".to_string(),
+            ));
+        }
+        self.retry = Some(policy);
+        Ok(self)
+    }
+
+    /// Send requests through a custom transport, e.g. a [`MockTransport`](crate::transport::MockTransport)
+    /// in tests. Transport and TLS settings of the builder are ignored.
+    pub fn set_transport(&mut self, transport: Arc<dyn HttpTransport>) -> Result<&mut Self, Error> {
+        self.custom_transport = Some(transport);
+        Ok(self)
+    }
+
+    /// Add an interceptor, see [`Interceptor`]. Interceptors run in the order they are added.
+    pub fn add_interceptor(
+        &mut self,
+        interceptor: Arc<dyn Interceptor>,
+    ) -> Result<&mut Self, Error> {
+        self.interceptors.push(interceptor);
+        Ok(self)
+    }
+
+    /// Throttle requests on the client side, see [`RateLimiter`]. The limiter is
+    /// shared by every clone of the built client.
+    pub fn set_rate_limiter(&mut self, config: RateLimiterConfig) -> Result<&mut Self, Error> {
+        if config.max_in_flight == Some(0) {
+            return Err(Error::Custom(
+                "rate limiter needs at least one request in flight".to_string(),
+            ));
+        }
+        self.rate_limit = Some(config);
+        Ok(self)
+    }
+
     pub fn build(&self) -> Result<Client, Error> {
         let Some(endpoint) = self.end_point.as_ref() else {
-            return Err(Error::Unknown);
+            return Err(Error::MissingSetting("endpoint".to_string()));
         };
         Ok(Client {
             end_point: endpoint.to_string(),
             end_point_realtime: self.end_point_realtime.clone(),
             header: self.header.clone(),
-            chunk_size: self.chunk_size.clone().unwrap_or_else(|| 5 * 1024 * 1024),
-            _self_signed: self.self_signed.clone().unwrap_or_else(|| false),
+            chunk_size: self.chunk_size.unwrap_or(5 * 1024 * 1024),
+            tls: self.tls.clone(),
+            retry: self.retry.clone(),
+            transport: match self.custom_transport.clone() {
+                Some(transport) => transport,
+                None => Arc::new(ReqwestTransport::new(self.transport.build(&self.tls)?)),
+            },
+            interceptors: self.interceptors.clone(),
+            rate_limiter: self
+                .rate_limit
+                .clone()
+                .map(|config| Arc::new(RateLimiter::new(config))),
         })
     }
 }
 
 impl Client {
+    /// Run service calls on a derived client and return their result together
+    /// with the metadata (status, headers, rate-limit) of the last response.
+    /// On failure the metadata is kept in the [`MetaError`], which converts
+    /// into [`Error`] with `?`.
+    /// ```no_run
+    /// # use unofficial_appwrite::{client::Client, error::Error, services::server::users::Users};
+    /// # async fn run(client: &Client) -> Result<(), Error> {
+    /// let user = client
+    ///     .with_meta(|client| async move { Users::get(&client, "user_id").await })
+    ///     .await?;
+    /// if user.meta.rate_limit.remaining == Some(0) {
+    ///     // back off until user.meta.rate_limit.reset
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn with_meta<T, F, Fut>(&self, f: F) -> Result<WithMeta<T>, MetaError>
+    where
+        F: FnOnce(Client) -> Fut,
+        Fut: Future<Output = Result<T, Error>>,
+    {
+        let capture = MetaCapture::default();
+        let last = capture.last.clone();
+        let mut client = self.clone();
+        client.interceptors.push(Arc::new(capture));
+
+        let result = f(client).await;
+        let meta = last.lock().expect("meta capture poisoned").take();
+        match result {
+            Ok(data) => Ok(WithMeta {
+                data,
+                meta: meta.unwrap_or_default(),
+            }),
+            Err(error) => Err(MetaError { error, meta }),
+        }
+    }
+
+    /// Derived client with `options` applied on top of its headers. The derived
+    /// client shares the transport, retry policy and rate limiter.
+    pub fn with_options(&self, options: &RequestOptions) -> Client {
+        let mut client = self.clone();
+        options.apply(&mut client.header);
+        client
+    }
+
+    /// Derived client running `interceptor` after the client's own.
+    pub(crate) fn with_interceptor(&self, interceptor: Arc<dyn Interceptor>) -> Client {
+        let mut client = self.clone();
+        client.interceptors.push(interceptor);
+        client
+    }
+
+    /// Derived client acting as the user of the session `secret`.
+    pub fn with_session(&self, secret: &str) -> Result<Client, Error> {
+        Ok(self.with_options(RequestOptions::new().set_session(secret)?))
+    }
+
+    /// Derived client acting as the user the `jwt` was issued for.
+    pub fn with_jwt(&self, jwt: &str) -> Result<Client, Error> {
+        Ok(self.with_options(RequestOptions::new().set_jwt(jwt)?))
+    }
+
+    pub fn with_locale(&self, locale: &str) -> Result<Client, Error> {
+        Ok(self.with_options(RequestOptions::new().set_locale(locale)?))
+    }
+
+    /// Derived client impersonating the user with this ID.
+    pub fn with_impersonate_user_id(&self, user_id: &str) -> Result<Client, Error> {
+        Ok(self.with_options(RequestOptions::new().set_impersonate_user_id(user_id)?))
+    }
+
+    /// Derived client impersonating the user with this email.
+    pub fn with_impersonate_user_email(&self, email: &str) -> Result<Client, Error> {
+        Ok(self.with_options(RequestOptions::new().set_impersonate_user_email(email)?))
+    }
+
+    /// Derived client impersonating the user with this phone number.
+    pub fn with_impersonate_user_phone(&self, phone: &str) -> Result<Client, Error> {
+        Ok(self.with_options(RequestOptions::new().set_impersonate_user_phone(phone)?))
+    }
+
+    pub(crate) async fn connect_websocket(
+        &self,
+        url: &str,
+    ) -> Result<WebSocketStream<MaybeTlsStream<TcpStream>>, Error> {
+        self.tls.connect_websocket(url).await
+    }
+
     pub async fn call(
         &self,
         method: HttpMethod,
@@ -140,96 +611,184 @@ impl Client {
         params: &HashMap<String, Value>,
         form: Option<Form>,
     ) -> Result<Response, Error> {
-        let res = reqwest::Client::new();
-        let res = match method {
-            HttpMethod::GET => {
-                let param = Self::_flatten_params_for_get(&json!(params))?;
-                res.get(format!("{}{}{}", self.end_point, path, param))
-            }
-            HttpMethod::POST => res
-                .post(format!("{}{}", self.end_point, path))
-                .json(&params),
-            HttpMethod::PUT => res.put(format!("{}{}", self.end_point, path)).json(&params),
-            HttpMethod::DELETE => res
-                .delete(format!("{}{}", self.end_point, path))
-                .json(&params),
-            HttpMethod::PATCH => res
-                .patch(format!("{}{}", self.end_point, path))
-                .json(&params),
-        };
-        let res = res.headers(headers).headers(self.header.clone());
+        // a multipart body can only be sent once, see `call_multipart` for retried uploads
+        let retry = form.is_none()
+            && self
+                .retry
+                .as_ref()
+                .is_some_and(|policy| policy.allows_method(method));
+        let mut form = form;
+        self.call_retrying(method, path, &headers, params, retry, || form.take())
+            .await
+    }
+
+    /// Send a multipart request, rebuilding the form for each attempt so a
+    /// failed upload chunk is retried on its own.
+    async fn call_multipart<B>(
+        &self,
+        path: &str,
+        headers: HeaderMap,
+        params: &HashMap<String, Value>,
+        build_form: B,
+    ) -> Result<Response, Error>
+    where
+        B: Fn() -> Form,
+    {
+        let retry = self.retry.is_some();
+        self.call_retrying(HttpMethod::POST, path, &headers, params, retry, || {
+            Some(build_form())
+        })
+        .await
+    }
 
-        let res = if let Some(form) = form {
-            res.multipart(form)
-        } else {
-            res
+    /// Send a request, resending it under the retry policy when `retry`.
+    /// `next_form` gives the multipart body of each attempt.
+    async fn call_retrying<F>(
+        &self,
+        method: HttpMethod,
+        path: &str,
+        headers: &HeaderMap,
+        params: &HashMap<String, Value>,
+        retry: bool,
+        mut next_form: F,
+    ) -> Result<Response, Error>
+    where
+        F: FnMut() -> Option<Form>,
+    {
+        let policy = self.retry.as_ref().filter(|_| retry);
+        let max_attempts = policy.map_or(1, |policy| policy.max_attempts);
+        let url = match method {
+            HttpMethod::GET => format!(
+                "{}{}{}",
+                self.end_point,
+                path,
+                encode_query(&json!(params))?
+            ),
+            _ => format!("{}{}", self.end_point, path),
         };
-        let res = res.send().await?;
-        // if res.status() >= StatusCode::BAD_REQUEST {
-        //     return Err(Error::AppWriteError(res.json::<AppWriteError>().await?));
-        // }
-        match res.status().as_u16() {
-            200..=299 => Ok(res),
-            400..=599 => {
-                let err = res.json::<AppWriteError>().await?;
-                Err(Error::AppWriteError {
-                    message: err.message,
-                    code: err.code,
-                    response: err.response,
-                    error_type: err.error_type,
-                })
-            }
-            _ => {
-                //TODO: Error Message: Unexpected status code:{}, response.statuscode
-                Err(Error::Unknown)
+        let mut attempt = 1;
+        loop {
+            let res = self
+                .send(method, &url, path, headers, params, next_form())
+                .await;
+            let delay = match (&res, policy) {
+                (_, _) if attempt >= max_attempts => None,
+                (Ok(res), Some(policy)) if policy.is_retryable_status(res.status().as_u16()) => {
+                    policy.delay_for_response(attempt, res.headers())
+                }
+                (Err(err), Some(policy)) if policy.is_retryable(err) => {
+                    Some(policy.backoff(attempt))
+                }
+                _ => None,
+            };
+            match delay {
+                Some(delay) => {
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                None => return Self::check_status(method, path, res?).await,
             }
         }
     }
 
-    fn _flatten_params_for_get(api_params: &serde_json::Value) -> Result<String, Error> {
-        let mut params_chain = String::new();
-        let mut i = 0;
+    async fn send(
+        &self,
+        method: HttpMethod,
+        url: &str,
+        path: &str,
+        headers: &HeaderMap,
+        params: &HashMap<String, Value>,
+        form: Option<Form>,
+    ) -> Result<Response, Error> {
+        let mut merged = headers.clone();
+        merged.extend(self.header.clone());
+        let body = match (method, form) {
+            (_, Some(form)) => RequestBody::Multipart(form),
+            (HttpMethod::GET, None) => RequestBody::Empty,
+            (_, None) => RequestBody::Json(json!(params)),
+        };
+        let mut request = TransportRequest {
+            method,
+            url: url.to_string(),
+            path: path.to_string(),
+            headers: merged,
+            body,
+        };
 
-        api_params
-            .as_object()
-            .ok_or(Error::Custom(
-                "Unable to convert value because it's not an object".to_string(),
-            ))?
-            .iter()
-            .for_each(|value| match value.0.contains("queries") {
-                true => {
-                    let v = value.1.as_array().unwrap();
-                    v.iter().for_each(|query| {
-                        let start_string = match i == 0 {
-                            true => "?",
-                            false => "&",
-                        };
-                        params_chain.push_str(
-                            format!("{}queries[{}]={}", start_string, i, query.as_str().unwrap())
-                                .as_str(),
-                        );
-                        i += 1;
-                    });
+        let mut seen = 0;
+        let mut short_circuit = None;
+        for interceptor in &self.interceptors {
+            match interceptor.on_request(&mut request).await? {
+                RequestFlow::Continue => seen += 1,
+                RequestFlow::Respond(res) => {
+                    short_circuit = Some(res);
+                    break;
                 }
-                false => {
-                    let start_character = match i != 0 {
-                        true => "&",
-                        false => "?",
-                    };
-                    params_chain.push_str(
-                        format!(
-                            "{}{}={}",
-                            start_character,
-                            value.0,
-                            value.1.as_str().unwrap()
-                        )
-                        .as_str(),
-                    )
+            }
+        }
+        let parts = RequestParts::from(&request);
+        let mut res = match short_circuit {
+            Some(res) => res,
+            None => match self.rate_limiter.as_ref() {
+                Some(limiter) => {
+                    let permit = limiter.acquire(method, path).await;
+                    let res = self.transport.send(request).await?;
+                    limiter.observe(&permit, &res);
+                    res
                 }
-            });
-        Ok(params_chain)
+                None => self.transport.send(request).await?,
+            },
+        };
+        for interceptor in self.interceptors[..seen].iter().rev() {
+            res = interceptor.on_response(&parts, res).await?;
+        }
+        Ok(res)
     }
 
+    async fn check_status(
+        method: HttpMethod,
+        path: &str,
+        res: Response,
+    ) -> Result<Response, Error> {
+        let status = res.status().as_u16();
+        if let 200..=299 = status {
+            return Ok(res);
+        }
+        let content_type = res
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(String::from);
+        let body = res.text().await?;
+        match status {
+            400..=599 => match serde_json::from_str::<AppWriteError>(&body) {
+                Ok(err) => Err(Error::AppWriteError {
+                    message: err.message,
+                    code: err.code,
+                    response: err.response,
+                    error_type: err.error_type,
+                    status,
+                    version: err.version,
+                }),
+                Err(_) => Err(Error::UnexpectedResponse {
+                    status,
+                    content_type,
+                    method,
+                    path: path.to_string(),
+                    body: truncate_body(body),
+                }),
+            },
+            _ => Err(Error::UnexpectedStatus {
+                status,
+                content_type,
+                method,
+                path: path.to_string(),
+                body: truncate_body(body),
+            }),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub async fn chunk_upload_file<F>(
         &self,
         file_path: String,
@@ -253,11 +812,12 @@ impl Client {
         //let uri = api_path;
 
         if file_size <= self.chunk_size {
-            let part = Part::bytes(file.clone()).file_name(file_name.clone());
-
-            let form = multipart::Form::new()
-                .text("fileId", file_id.clone())
-                .part("file", part);
+            let build_form = || {
+                multipart::Form::new().text("fileId", file_id.clone()).part(
+                    "file",
+                    Part::bytes(file.clone()).file_name(file_name.clone()),
+                )
+            };
             let mut headers = HeaderMap::new();
             headers.insert(
                 CONTENT_TYPE,
@@ -267,7 +827,7 @@ impl Client {
             );
 
             let res = self
-                .call(HttpMethod::POST, &api_path, headers, &params, Some(form))
+                .call_multipart(&api_path, headers, &params, build_form)
                 .await?;
             match is_file {
                 true => {
@@ -328,11 +888,12 @@ impl Client {
                 "Content-Range",
                 HeaderValue::from_str(content_range.as_str())?,
             );
-            let mut chunk_form = multipart::Form::new();
-            chunk_form = chunk_form.text("fileId", file_id.clone()).part(
-                "file",
-                Part::bytes(chunk.to_vec()).file_name(file_name.clone()),
-            );
+            let build_chunk_form = || {
+                multipart::Form::new().text("fileId", file_id.clone()).part(
+                    "file",
+                    Part::bytes(chunk.to_vec()).file_name(file_name.clone()),
+                )
+            };
             if !first_upload {
                 headers.insert(
                     "x-appwrite-id",
@@ -351,22 +912,19 @@ impl Client {
             }
 
             let response = self
-                .call(
-                    HttpMethod::POST,
-                    &api_path,
-                    headers,
-                    &params,
-                    Some(chunk_form),
-                )
+                .call_multipart(&api_path, headers, &params, build_chunk_form)
                 .await?;
 
             if response.status() != StatusCode::CREATED {
+                let status = response.status().as_u16();
                 let err = response.json::<AppWriteError>().await?;
                 return Err(Error::AppWriteError {
                     message: err.message,
                     code: err.code,
                     response: err.response,
                     error_type: err.error_type,
+                    status,
+                    version: err.version,
                 });
             }
 
@@ -415,7 +973,7 @@ impl Client {
 
             offset += self.chunk_size;
         }
-        Ok(res.ok_or(Error::Custom("No Upload Type".to_string()))?)
+        res.ok_or(Error::Custom("No Upload Type".to_string()))
 
         // !-> how to use
         // let progress_callback = |progress: ChunkProgress| {
@@ -436,3 +994,120 @@ impl Client {
         // .await?;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use std::{collections::HashMap, sync::Arc, time::Duration};
+
+    use serde_json::json;
+
+    use super::{split_pem_bundle, ClientBuilder, TlsConfig, TransportConfig};
+    use crate::{
+        enumm::HttpMethod,
+        error::Error,
+        retry::RetryPolicy,
+        transport::{MockResponse, MockTransport},
+    };
+
+    #[test]
+    fn test_split_pem_bundle() {
+        let bundle = b"# root\n-----BEGIN CERTIFICATE-----\nAAA\n-----END CERTIFICATE-----\n-----BEGIN CERTIFICATE-----\nBBB\n-----END CERTIFICATE-----\n";
+        let certs = split_pem_bundle(bundle);
+        assert_eq!(certs.len(), 2);
+        assert!(certs[1].starts_with(b"-----BEGIN CERTIFICATE-----\nBBB"));
+        assert!(split_pem_bundle(b"not a certificate").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_http2_preference_falls_back_to_http1() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0; 1024];
+                let _ = socket.read(&mut buf).await;
+                let _ = socket
+                    .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok")
+                    .await;
+            }
+        });
+
+        let preferred = TransportConfig {
+            http2_preferred: true,
+            ..Default::default()
+        };
+        let response = preferred
+            .build(&TlsConfig::default())
+            .unwrap()
+            .get(&url)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.version(), reqwest::Version::HTTP_11);
+
+        let forced = TransportConfig {
+            http2_prior_knowledge: true,
+            ..Default::default()
+        };
+        let client = forced.build(&TlsConfig::default()).unwrap();
+        assert!(client.get(&url).send().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_upload_retries_proxy_errors_with_retry_after() -> Result<(), Error> {
+        let mock = Arc::new(MockTransport::new());
+        let client = ClientBuilder::default()
+            .set_transport(mock.clone())?
+            .set_retry_policy(RetryPolicy {
+                max_delay: Duration::from_secs(1),
+                ..Default::default()
+            })?
+            .build()?;
+        let path = "/storage/buckets/photos/files";
+        let gateway = |retry_after: &str| {
+            MockResponse::bytes(503, "<html>Service Unavailable</html>")
+                .with_header("content-type", "text/html")?
+                .with_header("retry-after", retry_after)
+        };
+        mock.on(HttpMethod::POST, path, gateway("0")?)
+            .on(
+                HttpMethod::POST,
+                path,
+                MockResponse::json(
+                    201,
+                    json!({"$id": "a", "bucketId": "photos", "$createdAt": "", "$updatedAt": "",
+                           "$permissions": [], "name": "a.txt", "signature": "", "mimeType": "text/plain",
+                           "sizeOriginal": 1, "chunksTotal": 1, "chunksUploaded": 1}),
+                ),
+            )
+            .on(HttpMethod::POST, path, gateway("3600")?);
+
+        let file = std::env::temp_dir().join(format!("upload-{}.txt", uuid::Uuid::new_v4()));
+        std::fs::write(&file, "a")?;
+        let upload = || {
+            client.chunk_upload_file(
+                file.display().to_string(),
+                path.to_string(),
+                "a".to_string(),
+                HashMap::new(),
+                "a.txt".to_string(),
+                true,
+                |_| {},
+            )
+        };
+        upload().await?;
+        assert_eq!(mock.requests().len(), 2);
+
+        // asked to wait longer than `max_delay`: not retried
+        let err = upload().await.unwrap_err();
+        assert!(matches!(err, Error::UnexpectedResponse { status: 503, .. }));
+        assert_eq!(mock.requests().len(), 3);
+        std::fs::remove_file(file)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,273 @@
+//! # Config
+//!
+//! Client settings read from the environment or from named profiles in a TOML
+//! or JSON file, see [`ClientBuilder::from_env`] and
+//! [`ClientBuilder::from_profile`].
+//!
+//! A profile file lists profiles by name. A JSON file without a `profiles`
+//! table, such as the `appwrite.json` written by the Appwrite CLI, is read as
+//! the `default` profile.
+//! ```toml
+//! [profiles.default]
+//! endpoint = "https://cloud.appwrite.io/v1"
+//! project_id = "my-project"
+//! api_key = "standard_..."
+//!
+//! [profiles.local]
+//! endpoint = "https://localhost/v1"
+//! project_id = "my-project"
+//! self_signed = true
+//! ```
+use std::{collections::HashMap, path::Path};
+
+use serde::Deserialize;
+
+use crate::{client::ClientBuilder, error::Error};
+
+pub const ENV_ENDPOINT: &str = "APPWRITE_ENDPOINT";
+pub const ENV_PROJECT_ID: &str = "APPWRITE_PROJECT_ID";
+pub const ENV_API_KEY: &str = "APPWRITE_API_KEY";
+pub const ENV_SELF_SIGNED: &str = "APPWRITE_SELF_SIGNED";
+pub const ENV_LOCALE: &str = "APPWRITE_LOCALE";
+
+/// Name of the profile used when a file has no `profiles` table.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Connection settings of one Appwrite project.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct Profile {
+    pub endpoint: Option<String>,
+    #[serde(alias = "projectId")]
+    pub project_id: Option<String>,
+    #[serde(alias = "apiKey", alias = "key")]
+    pub api_key: Option<String>,
+    #[serde(alias = "selfSigned")]
+    pub self_signed: Option<bool>,
+    pub locale: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProfileFile {
+    #[serde(default)]
+    profiles: HashMap<String, Profile>,
+    #[serde(flatten)]
+    default: Profile,
+}
+
+/// Format of a profile file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Toml,
+    Json,
+}
+
+impl ConfigFormat {
+    /// Format from the file extension, JSON unless it is `.toml`.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("toml") => Self::Toml,
+            _ => Self::Json,
+        }
+    }
+}
+
+impl Profile {
+    /// Read the `APPWRITE_*` environment variables.
+    pub fn from_env() -> Result<Self, Error> {
+        Self::from_vars(|key| std::env::var(key).ok())
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<Self, Error> {
+        let var = |key: &str| var(key).filter(|value| !value.trim().is_empty());
+        let self_signed = match var(ENV_SELF_SIGNED) {
+            Some(value) => Some(parse_bool(&value).ok_or_else(|| Error::InvalidSetting {
+                setting: ENV_SELF_SIGNED.to_string(),
+                message: format!("expected true or false, got `{}`", value),
+            })?),
+            None => None,
+        };
+        Ok(Self {
+            endpoint: var(ENV_ENDPOINT),
+            project_id: var(ENV_PROJECT_ID),
+            api_key: var(ENV_API_KEY),
+            self_signed,
+            locale: var(ENV_LOCALE),
+        })
+    }
+
+    /// Read profile `name` from a TOML or JSON file.
+    pub fn load(path: impl AsRef<Path>, name: &str) -> Result<Self, Error> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Err(Error::FilePathNotExist(path.display().to_string()));
+        }
+        let contents = std::fs::read_to_string(path).map_err(|err| Error::ConfigFile {
+            path: path.display().to_string(),
+            message: err.to_string(),
+        })?;
+        Self::parse(&contents, ConfigFormat::from_path(path), name).map_err(|err| match err {
+            Error::ConfigFile { message, .. } => Error::ConfigFile {
+                path: path.display().to_string(),
+                message,
+            },
+            err => err,
+        })
+    }
+
+    /// Read profile `name` from the contents of a profile file.
+    pub fn parse(contents: &str, format: ConfigFormat, name: &str) -> Result<Self, Error> {
+        let file: ProfileFile = match format {
+            ConfigFormat::Toml => toml::from_str(contents).map_err(|err| err.to_string()),
+            ConfigFormat::Json => serde_json::from_str(contents).map_err(|err| err.to_string()),
+        }
+        .map_err(|message| Error::ConfigFile {
+            path: "<memory>".to_string(),
+            message,
+        })?;
+        let ProfileFile {
+            mut profiles,
+            default,
+        } = file;
+        if let Some(profile) = profiles.remove(name) {
+            return Ok(profile);
+        }
+        if name == DEFAULT_PROFILE && default != Profile::default() {
+            return Ok(default);
+        }
+        Err(Error::MissingSetting(format!("profile `{}`", name)))
+    }
+
+    /// Apply the profile to `builder`. `source` names where the settings came
+    /// from and is used in validation errors.
+    fn apply(&self, builder: &mut ClientBuilder, source: &str) -> Result<(), Error> {
+        let missing = |setting: &str| Error::MissingSetting(format!("{} ({})", setting, source));
+        let endpoint = self
+            .endpoint
+            .as_deref()
+            .ok_or_else(|| missing("endpoint"))?;
+        let project_id = self
+            .project_id
+            .as_deref()
+            .ok_or_else(|| missing("project id"))?;
+
+        builder.end_point_realtime = None;
+        builder.set_endpoint(endpoint)?.set_project(project_id)?;
+        if let Some(key) = self.api_key.as_deref() {
+            builder.set_key(key)?;
+        }
+        if let Some(locale) = self.locale.as_deref() {
+            builder.set_locale(locale)?;
+        }
+        if let Some(self_signed) = self.self_signed {
+            builder.set_self_signed(self_signed)?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+impl ClientBuilder {
+    /// Builder configured from `APPWRITE_ENDPOINT`, `APPWRITE_PROJECT_ID`,
+    /// `APPWRITE_API_KEY`, `APPWRITE_SELF_SIGNED` and `APPWRITE_LOCALE`.
+    ///
+    /// The endpoint and project id are required.
+    /// ```no_run
+    /// use unofficial_appwrite::client::ClientBuilder;
+    ///
+    /// let client = ClientBuilder::from_env()?.build()?;
+    /// # Ok::<(), unofficial_appwrite::error::Error>(())
+    /// ```
+    pub fn from_env() -> Result<Self, Error> {
+        let mut builder = ClientBuilder::new();
+        Profile::from_env()?.apply(&mut builder, "environment")?;
+        Ok(builder)
+    }
+
+    /// Builder configured from profile `name` of a TOML or JSON file.
+    /// ```no_run
+    /// use unofficial_appwrite::client::ClientBuilder;
+    ///
+    /// let client = ClientBuilder::from_profile("appwrite.toml", "local")?.build()?;
+    /// # Ok::<(), unofficial_appwrite::error::Error>(())
+    /// ```
+    pub fn from_profile(path: impl AsRef<Path>, name: &str) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let mut builder = ClientBuilder::new();
+        Profile::load(path, name)?.apply(
+            &mut builder,
+            &format!("profile `{}` in {}", name, path.display()),
+        )?;
+        Ok(builder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_from_vars() -> Result<(), Error> {
+        let vars = HashMap::from([
+            (ENV_ENDPOINT, "https://localhost/v1"),
+            (ENV_PROJECT_ID, "project"),
+            (ENV_SELF_SIGNED, "true"),
+        ]);
+        let profile = Profile::from_vars(|key| vars.get(key).map(|v| v.to_string()))?;
+        assert_eq!(profile.self_signed, Some(true));
+        assert_eq!(profile.api_key, None);
+
+        let mut builder = ClientBuilder::new();
+        profile.apply(&mut builder, "environment")?;
+        assert_eq!(
+            builder.end_point_realtime.as_deref(),
+            Some("wss://localhost/v1")
+        );
+
+        let err =
+            Profile::from_vars(|key| (key == ENV_SELF_SIGNED).then(|| "maybe".into())).unwrap_err();
+        assert!(matches!(err, Error::InvalidSetting { setting, .. } if setting == ENV_SELF_SIGNED));
+
+        let err = Profile::from_vars(|_| None)?
+            .apply(&mut ClientBuilder::new(), "environment")
+            .unwrap_err();
+        assert_eq!(err.to_string(), "missing setting: endpoint (environment)");
+        Ok(())
+    }
+
+    #[test]
+    fn test_profile_files() -> Result<(), Error> {
+        let toml = r#"
+            [profiles.local]
+            endpoint = "https://localhost/v1"
+            project_id = "project"
+            self_signed = true
+        "#;
+        let profile = Profile::parse(toml, ConfigFormat::Toml, "local")?;
+        assert_eq!(profile.project_id.as_deref(), Some("project"));
+        assert!(matches!(
+            Profile::parse(toml, ConfigFormat::Toml, "prod"),
+            Err(Error::MissingSetting(setting)) if setting == "profile `prod`"
+        ));
+
+        let appwrite_json =
+            r#"{"projectId": "project", "endpoint": "https://cloud.appwrite.io/v1"}"#;
+        let profile = Profile::parse(appwrite_json, ConfigFormat::Json, DEFAULT_PROFILE)?;
+        assert_eq!(profile.project_id.as_deref(), Some("project"));
+
+        let err = Profile::parse(r#"{"projectId": "project"}"#, ConfigFormat::Json, "default")?
+            .apply(&mut ClientBuilder::new(), "profile `default`")
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "missing setting: endpoint (profile `default`)"
+        );
+        Ok(())
+    }
+}
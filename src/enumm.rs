@@ -1,4 +1,5 @@
 /// HTTP methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum HttpMethod {
     GET,
     POST,
@@ -7,6 +8,13 @@ pub enum HttpMethod {
     PATCH,
 }
 
+impl HttpMethod {
+    /// Whether sending the same request twice has the same effect as sending it once.
+    pub fn is_idempotent(&self) -> bool {
+        matches!(self, Self::GET | Self::PUT | Self::DELETE)
+    }
+}
+
 /// Response Types
 pub enum ResponseType {
     /// Transform the response data to JSON object only when the
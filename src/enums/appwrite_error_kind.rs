@@ -0,0 +1,342 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Appwrite error types.
+///
+/// See [Error Types](https://appwrite.io/docs/advanced/platform/response-codes#errorTypes)
+/// for more information. Types unknown to this SDK are kept in [`AppwriteErrorKind::Other`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum AppwriteErrorKind {
+    // General
+    #[serde(rename = "general_mock")]
+    GeneralMock,
+    #[serde(rename = "general_argument_invalid")]
+    GeneralArgumentInvalid,
+    #[serde(rename = "general_query_limit_exceeded")]
+    GeneralQueryLimitExceeded,
+    #[serde(rename = "general_query_invalid")]
+    GeneralQueryInvalid,
+    #[serde(rename = "general_cursor_not_found")]
+    GeneralCursorNotFound,
+    #[serde(rename = "general_server_error")]
+    GeneralServerError,
+    #[serde(rename = "general_protocol_unsupported")]
+    GeneralProtocolUnsupported,
+    #[serde(rename = "general_codes_disabled")]
+    GeneralCodesDisabled,
+    #[serde(rename = "general_usage_disabled")]
+    GeneralUsageDisabled,
+    #[serde(rename = "general_not_implemented")]
+    GeneralNotImplemented,
+    #[serde(rename = "general_invalid_email")]
+    GeneralInvalidEmail,
+    #[serde(rename = "general_invalid_phone")]
+    GeneralInvalidPhone,
+    #[serde(rename = "general_route_not_found")]
+    GeneralRouteNotFound,
+    #[serde(rename = "general_unknown_origin")]
+    GeneralUnknownOrigin,
+    #[serde(rename = "general_service_disabled")]
+    GeneralServiceDisabled,
+    #[serde(rename = "general_unauthorized_scope")]
+    GeneralUnauthorizedScope,
+    #[serde(rename = "general_rate_limit_exceeded")]
+    GeneralRateLimitExceeded,
+    #[serde(rename = "general_access_forbidden")]
+    GeneralAccessForbidden,
+    #[serde(rename = "general_bad_request")]
+    GeneralBadRequest,
+    #[serde(rename = "general_phone_disabled")]
+    GeneralPhoneDisabled,
+    #[serde(rename = "general_smtp_disabled")]
+    GeneralSmtpDisabled,
+    #[serde(rename = "general_unknown")]
+    GeneralUnknown,
+    // Users
+    #[serde(rename = "user_count_exceeded")]
+    UserCountExceeded,
+    #[serde(rename = "user_jwt_invalid")]
+    UserJwtInvalid,
+    #[serde(rename = "user_blocked")]
+    UserBlocked,
+    #[serde(rename = "user_invalid_token")]
+    UserInvalidToken,
+    #[serde(rename = "user_email_not_whitelisted")]
+    UserEmailNotWhitelisted,
+    #[serde(rename = "user_invalid_code")]
+    UserInvalidCode,
+    #[serde(rename = "user_ip_not_whitelisted")]
+    UserIpNotWhitelisted,
+    #[serde(rename = "user_invalid_credentials")]
+    UserInvalidCredentials,
+    #[serde(rename = "user_anonymous_console_prohibited")]
+    UserAnonymousConsoleProhibited,
+    #[serde(rename = "user_session_already_exists")]
+    UserSessionAlreadyExists,
+    #[serde(rename = "user_unauthorized")]
+    UserUnauthorized,
+    #[serde(rename = "user_oauth2_unauthorized")]
+    UserOAuth2Unauthorized,
+    #[serde(rename = "user_oauth2_bad_request")]
+    UserOAuth2BadRequest,
+    #[serde(rename = "user_oauth2_provider_error")]
+    UserOAuth2ProviderError,
+    #[serde(rename = "user_password_reset_required")]
+    UserPasswordResetRequired,
+    #[serde(rename = "user_already_exists")]
+    UserAlreadyExists,
+    #[serde(rename = "user_email_already_exists")]
+    UserEmailAlreadyExists,
+    #[serde(rename = "user_phone_already_exists")]
+    UserPhoneAlreadyExists,
+    #[serde(rename = "user_password_mismatch")]
+    UserPasswordMismatch,
+    #[serde(rename = "user_password_recently_used")]
+    UserPasswordRecentlyUsed,
+    #[serde(rename = "user_password_personal_data")]
+    UserPasswordPersonalData,
+    #[serde(rename = "user_session_not_found")]
+    UserSessionNotFound,
+    #[serde(rename = "user_identity_not_found")]
+    UserIdentityNotFound,
+    #[serde(rename = "user_not_found")]
+    UserNotFound,
+    #[serde(rename = "user_target_not_found")]
+    UserTargetNotFound,
+    #[serde(rename = "user_phone_not_found")]
+    UserPhoneNotFound,
+    #[serde(rename = "user_missing_id")]
+    UserMissingId,
+    #[serde(rename = "user_more_factors_required")]
+    UserMoreFactorsRequired,
+    #[serde(rename = "user_challenge_required")]
+    UserChallengeRequired,
+    // Teams
+    #[serde(rename = "team_not_found")]
+    TeamNotFound,
+    #[serde(rename = "team_already_exists")]
+    TeamAlreadyExists,
+    #[serde(rename = "team_invite_already_exists")]
+    TeamInviteAlreadyExists,
+    #[serde(rename = "team_invite_not_found")]
+    TeamInviteNotFound,
+    #[serde(rename = "team_invalid_secret")]
+    TeamInvalidSecret,
+    #[serde(rename = "team_membership_mismatch")]
+    TeamMembershipMismatch,
+    #[serde(rename = "team_invite_mismatch")]
+    TeamInviteMismatch,
+    #[serde(rename = "membership_not_found")]
+    MembershipNotFound,
+    #[serde(rename = "membership_already_confirmed")]
+    MembershipAlreadyConfirmed,
+    // Avatars
+    #[serde(rename = "avatar_set_not_found")]
+    AvatarSetNotFound,
+    #[serde(rename = "avatar_not_found")]
+    AvatarNotFound,
+    #[serde(rename = "avatar_image_not_found")]
+    AvatarImageNotFound,
+    #[serde(rename = "avatar_remote_url_failed")]
+    AvatarRemoteUrlFailed,
+    #[serde(rename = "avatar_icon_not_found")]
+    AvatarIconNotFound,
+    // Storage
+    #[serde(rename = "storage_file_not_found")]
+    StorageFileNotFound,
+    #[serde(rename = "storage_file_already_exists")]
+    StorageFileAlreadyExists,
+    #[serde(rename = "storage_file_not_readable")]
+    StorageFileNotReadable,
+    #[serde(rename = "storage_device_not_found")]
+    StorageDeviceNotFound,
+    #[serde(rename = "storage_file_empty")]
+    StorageFileEmpty,
+    #[serde(rename = "storage_file_type_unsupported")]
+    StorageFileTypeUnsupported,
+    #[serde(rename = "storage_invalid_file_size")]
+    StorageInvalidFileSize,
+    #[serde(rename = "storage_invalid_file")]
+    StorageInvalidFile,
+    #[serde(rename = "storage_bucket_already_exists")]
+    StorageBucketAlreadyExists,
+    #[serde(rename = "storage_bucket_not_found")]
+    StorageBucketNotFound,
+    #[serde(rename = "storage_invalid_content_range")]
+    StorageInvalidContentRange,
+    #[serde(rename = "storage_invalid_range")]
+    StorageInvalidRange,
+    #[serde(rename = "storage_invalid_appwrite_id")]
+    StorageInvalidAppwriteId,
+    // Functions
+    #[serde(rename = "function_not_found")]
+    FunctionNotFound,
+    #[serde(rename = "function_runtime_unsupported")]
+    FunctionRuntimeUnsupported,
+    #[serde(rename = "function_entrypoint_missing")]
+    FunctionEntrypointMissing,
+    #[serde(rename = "build_not_found")]
+    BuildNotFound,
+    #[serde(rename = "build_not_ready")]
+    BuildNotReady,
+    #[serde(rename = "build_in_progress")]
+    BuildInProgress,
+    #[serde(rename = "deployment_not_found")]
+    DeploymentNotFound,
+    #[serde(rename = "execution_not_found")]
+    ExecutionNotFound,
+    #[serde(rename = "variable_not_found")]
+    VariableNotFound,
+    #[serde(rename = "variable_already_exists")]
+    VariableAlreadyExists,
+    // Databases
+    #[serde(rename = "database_not_found")]
+    DatabaseNotFound,
+    #[serde(rename = "database_already_exists")]
+    DatabaseAlreadyExists,
+    #[serde(rename = "database_timeout")]
+    DatabaseTimeout,
+    #[serde(rename = "collection_not_found")]
+    CollectionNotFound,
+    #[serde(rename = "collection_already_exists")]
+    CollectionAlreadyExists,
+    #[serde(rename = "collection_limit_exceeded")]
+    CollectionLimitExceeded,
+    #[serde(rename = "document_not_found")]
+    DocumentNotFound,
+    #[serde(rename = "document_invalid_structure")]
+    DocumentInvalidStructure,
+    #[serde(rename = "document_missing_data")]
+    DocumentMissingData,
+    #[serde(rename = "document_missing_payload")]
+    DocumentMissingPayload,
+    #[serde(rename = "document_already_exists")]
+    DocumentAlreadyExists,
+    #[serde(rename = "document_update_conflict")]
+    DocumentUpdateConflict,
+    #[serde(rename = "document_delete_restricted")]
+    DocumentDeleteRestricted,
+    #[serde(rename = "attribute_not_found")]
+    AttributeNotFound,
+    #[serde(rename = "attribute_unknown")]
+    AttributeUnknown,
+    #[serde(rename = "attribute_not_available")]
+    AttributeNotAvailable,
+    #[serde(rename = "attribute_format_unsupported")]
+    AttributeFormatUnsupported,
+    #[serde(rename = "attribute_default_unsupported")]
+    AttributeDefaultUnsupported,
+    #[serde(rename = "attribute_already_exists")]
+    AttributeAlreadyExists,
+    #[serde(rename = "attribute_limit_exceeded")]
+    AttributeLimitExceeded,
+    #[serde(rename = "attribute_value_invalid")]
+    AttributeValueInvalid,
+    #[serde(rename = "attribute_type_invalid")]
+    AttributeTypeInvalid,
+    #[serde(rename = "relationship_value_invalid")]
+    RelationshipValueInvalid,
+    #[serde(rename = "index_not_found")]
+    IndexNotFound,
+    #[serde(rename = "index_limit_exceeded")]
+    IndexLimitExceeded,
+    #[serde(rename = "index_already_exists")]
+    IndexAlreadyExists,
+    #[serde(rename = "index_invalid")]
+    IndexInvalid,
+    // Messaging
+    #[serde(rename = "provider_not_found")]
+    ProviderNotFound,
+    #[serde(rename = "provider_already_exists")]
+    ProviderAlreadyExists,
+    #[serde(rename = "topic_not_found")]
+    TopicNotFound,
+    #[serde(rename = "topic_already_exists")]
+    TopicAlreadyExists,
+    #[serde(rename = "subscriber_not_found")]
+    SubscriberNotFound,
+    #[serde(rename = "subscriber_already_exists")]
+    SubscriberAlreadyExists,
+    #[serde(rename = "message_not_found")]
+    MessageNotFound,
+    #[serde(rename = "message_already_exists")]
+    MessageAlreadyExists,
+    // Projects
+    #[serde(rename = "project_not_found")]
+    ProjectNotFound,
+    #[serde(rename = "project_unknown")]
+    ProjectUnknown,
+    #[serde(untagged)]
+    Other(String),
+}
+
+impl AppwriteErrorKind {
+    /// Converts the enum to its serialized string representation
+    pub fn as_serialized(&self) -> String {
+        serde_json::to_string(self)
+            .unwrap_or_else(|_| panic!("Failed to serialize AppwriteErrorKind: {:?}", self))
+            .trim_matches('"') // Remove surrounding quotes from the JSON string
+            .to_string()
+    }
+
+    /// A resource (document, user, bucket, ...) does not exist.
+    pub fn is_not_found(&self) -> bool {
+        self.as_serialized().ends_with("_not_found")
+    }
+
+    /// A resource with the same ID or unique value already exists.
+    pub fn is_conflict(&self) -> bool {
+        let kind = self.as_serialized();
+        kind.ends_with("_already_exists") || *self == Self::DocumentUpdateConflict
+    }
+
+    /// The caller is not authenticated or lacks the required scope.
+    pub fn is_unauthorized(&self) -> bool {
+        matches!(
+            self,
+            Self::GeneralUnauthorizedScope
+                | Self::GeneralAccessForbidden
+                | Self::UserUnauthorized
+                | Self::UserJwtInvalid
+                | Self::UserInvalidToken
+                | Self::UserInvalidCredentials
+                | Self::UserBlocked
+                | Self::UserMoreFactorsRequired
+        )
+    }
+
+    pub fn is_rate_limited(&self) -> bool {
+        *self == Self::GeneralRateLimitExceeded
+    }
+
+    /// The request was rejected because of invalid input.
+    pub fn is_validation(&self) -> bool {
+        let kind = self.as_serialized();
+        kind.ends_with("_invalid")
+            || kind.contains("_invalid_")
+            || kind.ends_with("_unsupported")
+            || kind.contains("_missing_")
+            || matches!(
+                self,
+                Self::GeneralBadRequest
+                    | Self::DocumentInvalidStructure
+                    | Self::UserPasswordMismatch
+                    | Self::UserPasswordRecentlyUsed
+                    | Self::UserPasswordPersonalData
+            )
+    }
+
+    pub fn is_server_error(&self) -> bool {
+        matches!(
+            self,
+            Self::GeneralServerError | Self::GeneralUnknown | Self::DatabaseTimeout
+        )
+    }
+}
+
+impl From<&str> for AppwriteErrorKind {
+    fn from(value: &str) -> Self {
+        serde_json::from_value(Value::String(value.to_string()))
+            .unwrap_or_else(|_| Self::Other(value.to_string()))
+    }
+}
@@ -1,3 +1,4 @@
+pub mod appwrite_error_kind;
 pub mod authentication_factor;
 pub mod authentication_type;
 pub mod browser;
@@ -2,18 +2,67 @@ use reqwest::header::{InvalidHeaderName, InvalidHeaderValue};
 use serde::Deserialize;
 use thiserror::Error;
 
+use crate::{enumm::HttpMethod, enums::appwrite_error_kind::AppwriteErrorKind};
+
+/// Maximum number of bytes of a raw response body kept in an error.
+pub const MAX_ERROR_BODY_LEN: usize = 1024;
+
+/// Truncate a raw body to [`MAX_ERROR_BODY_LEN`] bytes on a char boundary.
+pub(crate) fn truncate_body(mut body: String) -> String {
+    if body.len() > MAX_ERROR_BODY_LEN {
+        let mut end = MAX_ERROR_BODY_LEN;
+        while !body.is_char_boundary(end) {
+            end -= 1;
+        }
+        body.truncate(end);
+        body.push_str("...");
+    }
+    body
+}
+
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("file size error: {0:?}")]
     Io(#[from] std::io::Error),
     #[error("network error: {0:?}")]
     Network(#[from] reqwest::Error),
-    #[error("AppWrite error:-> code:{code:?} -> message:{message:?} -> response:{response:?} -> type:{error_type:?}")]
+    /// TLS failure, e.g. a [`native_tls::Error`] or a
+    /// [`PinMismatch`](crate::pinning::PinMismatch).
+    #[error("tls error: {0}")]
+    Tls(#[source] Box<dyn std::error::Error + Send + Sync>),
+    #[error("AppWrite error:-> status:{status} -> code:{code:?} -> message:{message:?} -> response:{response:?} -> type:{error_type:?}")]
     AppWriteError {
         message: String,
         code: Option<u64>,
         response: Option<String>,
         error_type: Option<String>,
+        /// HTTP status code of the response.
+        status: u16,
+        /// Appwrite server version.
+        version: Option<String>,
+    },
+
+    /// Error response whose body is not an Appwrite error, e.g. an HTML page
+    /// from a reverse proxy or an empty body.
+    #[error("unexpected {status} response to {method:?} {path} (content-type: {content_type:?}): {body}")]
+    UnexpectedResponse {
+        status: u16,
+        content_type: Option<String>,
+        method: HttpMethod,
+        path: String,
+        /// Raw body, truncated to [`MAX_ERROR_BODY_LEN`] bytes.
+        body: String,
+    },
+
+    /// Response with a status code outside of 200-599.
+    #[error("unexpected status code {status} for {method:?} {path} (content-type: {content_type:?}): {body}")]
+    UnexpectedStatus {
+        status: u16,
+        content_type: Option<String>,
+        method: HttpMethod,
+        path: String,
+        /// Raw body, truncated to [`MAX_ERROR_BODY_LEN`] bytes.
+        body: String,
     },
 
     #[error("invalid header name: {0:#?}")]
@@ -27,11 +76,144 @@ pub enum Error {
     #[error("Unknown error: probably a None Type")]
     Unknown,
 
+    #[error("missing setting: {0}")]
+    MissingSetting(String),
+
+    #[error("invalid setting {setting}: {message}")]
+    InvalidSetting { setting: String, message: String },
+
+    #[error("invalid config file {path}: {message}")]
+    ConfigFile { path: String, message: String },
+
+    #[error("invalid query: {0}")]
+    InvalidQuery(String),
+
+    #[error("failed to deserialize document `{document_id}` at `{path}`: {message}")]
+    DocumentData {
+        document_id: String,
+        /// Path of the field which failed, e.g. `tags[2]`.
+        path: String,
+        message: String,
+    },
+
+    #[error("failed to deserialize row `{row_id}` at `{path}`: {message}")]
+    RowData {
+        row_id: String,
+        /// Path of the field which failed, e.g. `tags[2]`.
+        path: String,
+        message: String,
+    },
+
+    #[error("migration {version} refused: {message}")]
+    Migration { version: String, message: String },
+
+    #[error("migration step `{step}` failed: {source}")]
+    MigrationStep {
+        step: String,
+        #[source]
+        source: Box<Error>,
+    },
+
+    /// Attribute or index which Appwrite could not create.
+    #[error("{resource} is {status}: {message}")]
+    ResourceFailed {
+        resource: String,
+        status: String,
+        /// `error` field of the resource.
+        message: String,
+    },
+
+    #[error("timed out after {timeout:?} waiting for {resource}, last status: {status}")]
+    WaitTimeout {
+        resource: String,
+        status: String,
+        timeout: std::time::Duration,
+    },
+
+    #[error("more than {0} items")]
+    TooManyItems(usize),
+
     #[error("wrong upload type")]
     WrongUploadType,
 
     #[error("Custom error: {0}")]
     Custom(String),
+
+    #[error("interceptor error: {0}")]
+    Interceptor(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl From<native_tls::Error> for Error {
+    fn from(err: native_tls::Error) -> Self {
+        Error::Tls(Box::new(err))
+    }
+}
+
+impl Error {
+    /// Typed Appwrite error type, `None` when the error did not come from Appwrite.
+    pub fn kind(&self) -> Option<AppwriteErrorKind> {
+        match self {
+            Error::AppWriteError {
+                error_type: Some(error_type),
+                ..
+            } => Some(AppwriteErrorKind::from(error_type.as_str())),
+            _ => None,
+        }
+    }
+
+    /// HTTP status code of an Appwrite error response.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Error::AppWriteError { status, .. }
+            | Error::UnexpectedResponse { status, .. }
+            | Error::UnexpectedStatus { status, .. } => Some(*status),
+            Error::Network(err) => err.status().map(|status| status.as_u16()),
+            _ => None,
+        }
+    }
+
+    fn matches(&self, status: &[u16], kind: impl Fn(&AppwriteErrorKind) -> bool) -> bool {
+        self.kind().is_some_and(|k| kind(&k))
+            || self.status().is_some_and(|code| status.contains(&code))
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        self.matches(&[404], AppwriteErrorKind::is_not_found)
+    }
+
+    pub fn is_conflict(&self) -> bool {
+        self.matches(&[409], AppwriteErrorKind::is_conflict)
+    }
+
+    pub fn is_unauthorized(&self) -> bool {
+        self.matches(&[401, 403], AppwriteErrorKind::is_unauthorized)
+    }
+
+    pub fn is_rate_limited(&self) -> bool {
+        self.matches(&[429], AppwriteErrorKind::is_rate_limited)
+    }
+
+    pub fn is_validation(&self) -> bool {
+        self.matches(&[400], AppwriteErrorKind::is_validation)
+    }
+
+    pub fn is_server_error(&self) -> bool {
+        self.status().is_some_and(|status| status >= 500)
+            || self.kind().is_some_and(|k| k.is_server_error())
+    }
+
+    /// Transient failure which is usually worth retrying.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::Network(err) => err.is_connect() || err.is_timeout(),
+            _ => {
+                self.is_rate_limited()
+                    || self
+                        .status()
+                        .is_some_and(|status| matches!(status, 502..=504))
+            }
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -57,3 +239,59 @@ impl std::fmt::Display for AppWriteError {
 }
 
 impl std::error::Error for AppWriteError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn appwrite_error(status: u16, error_type: &str) -> Error {
+        Error::AppWriteError {
+            message: String::new(),
+            code: Some(status.into()),
+            response: None,
+            error_type: Some(error_type.to_string()),
+            status,
+            version: Some("1.5.7".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_truncate_body() {
+        assert_eq!(truncate_body("short".to_string()), "short");
+        let body = truncate_body("é".repeat(MAX_ERROR_BODY_LEN));
+        assert!(body.len() <= MAX_ERROR_BODY_LEN + 3);
+        assert!(body.ends_with("..."));
+    }
+
+    #[test]
+    fn test_error_kind() {
+        let err = appwrite_error(404, "document_not_found");
+        assert_eq!(err.kind(), Some(AppwriteErrorKind::DocumentNotFound));
+        assert!(err.is_not_found());
+        assert!(!err.is_conflict());
+
+        let err = appwrite_error(409, "user_already_exists");
+        assert_eq!(err.kind(), Some(AppwriteErrorKind::UserAlreadyExists));
+        assert!(err.is_conflict());
+
+        let err = appwrite_error(429, "general_rate_limit_exceeded");
+        assert!(err.is_rate_limited());
+        assert!(err.is_retryable());
+
+        let err = appwrite_error(400, "attribute_value_invalid");
+        assert!(err.is_validation());
+        assert!(!err.is_server_error());
+
+        let err = appwrite_error(503, "some_future_type");
+        assert_eq!(
+            err.kind(),
+            Some(AppwriteErrorKind::Other("some_future_type".to_string()))
+        );
+        assert!(err.is_server_error());
+        assert!(err.is_retryable());
+        assert_eq!(
+            AppwriteErrorKind::UserOAuth2Unauthorized.as_serialized(),
+            "user_oauth2_unauthorized"
+        );
+    }
+}
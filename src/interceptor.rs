@@ -0,0 +1,164 @@
+//! # Interceptor
+//!
+//! Interceptors registered with
+//! [`ClientBuilder::add_interceptor`](crate::client::ClientBuilder::add_interceptor)
+//! see every request sent by [`Client::call`](crate::client::Client::call),
+//! including each chunk of a multipart upload, and every response received.
+use std::fmt::Debug;
+
+use futures_util::future::BoxFuture;
+use reqwest::{header::HeaderMap, Response};
+
+use crate::{enumm::HttpMethod, error::Error, transport::TransportRequest};
+
+/// Outcome of [`Interceptor::on_request`].
+#[derive(Debug)]
+pub enum RequestFlow {
+    /// Pass the request on to the next interceptor and then the transport.
+    Continue,
+    /// Skip the transport and answer with this response, e.g. from a cache.
+    Respond(Response),
+}
+
+/// Request as it was sent, handed to [`Interceptor::on_response`].
+#[derive(Debug, Clone)]
+pub struct RequestParts {
+    pub method: HttpMethod,
+    pub url: String,
+    pub path: String,
+    pub headers: HeaderMap,
+}
+
+impl From<&TransportRequest> for RequestParts {
+    fn from(request: &TransportRequest) -> Self {
+        Self {
+            method: request.method,
+            url: request.url.clone(),
+            path: request.path.clone(),
+            headers: request.headers.clone(),
+        }
+    }
+}
+
+/// Reads or changes outgoing requests and incoming responses.
+///
+/// Requests go through the interceptors in registration order and responses
+/// in reverse order. Returning an error fails the call with that error, use
+/// [`Error::Interceptor`] to carry a custom error type.
+pub trait Interceptor: Debug + Send + Sync {
+    fn on_request<'a>(
+        &'a self,
+        _request: &'a mut TransportRequest,
+    ) -> BoxFuture<'a, Result<RequestFlow, Error>> {
+        Box::pin(async { Ok(RequestFlow::Continue) })
+    }
+
+    fn on_response<'a>(
+        &'a self,
+        _request: &'a RequestParts,
+        response: Response,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(async { Ok(response) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Arc};
+
+    use reqwest::header::HeaderValue;
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        client::ClientBuilder,
+        services::server::users::Users,
+        transport::{MockResponse, MockTransport},
+    };
+
+    #[derive(Debug)]
+    struct CorrelationId;
+
+    impl Interceptor for CorrelationId {
+        fn on_request<'a>(
+            &'a self,
+            request: &'a mut TransportRequest,
+        ) -> BoxFuture<'a, Result<RequestFlow, Error>> {
+            Box::pin(async move {
+                request
+                    .headers
+                    .insert("x-correlation-id", HeaderValue::from_static("abc"));
+                Ok(RequestFlow::Continue)
+            })
+        }
+
+        fn on_response<'a>(
+            &'a self,
+            _request: &'a RequestParts,
+            mut response: Response,
+        ) -> BoxFuture<'a, Result<Response, Error>> {
+            Box::pin(async move {
+                response
+                    .headers_mut()
+                    .insert("x-seen", HeaderValue::from_static("1"));
+                Ok(response)
+            })
+        }
+    }
+
+    #[derive(Debug)]
+    struct Deny;
+
+    impl Interceptor for Deny {
+        fn on_request<'a>(
+            &'a self,
+            request: &'a mut TransportRequest,
+        ) -> BoxFuture<'a, Result<RequestFlow, Error>> {
+            Box::pin(async move {
+                match request.path.as_str() {
+                    "/users/cached" => Ok(RequestFlow::Respond(
+                        MockResponse::bytes(204, "").into_response()?,
+                    )),
+                    "/users/blocked" => Err(Error::Interceptor("blocked".into())),
+                    _ => Ok(RequestFlow::Continue),
+                }
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_interceptors() -> Result<(), Error> {
+        let mock = Arc::new(MockTransport::new());
+        mock.on(
+            HttpMethod::DELETE,
+            "/users/user",
+            MockResponse::json(204, json!({})),
+        );
+        let client = ClientBuilder::default()
+            .set_transport(mock.clone())?
+            .add_interceptor(Arc::new(CorrelationId))?
+            .add_interceptor(Arc::new(Deny))?
+            .build()?;
+
+        let response = client
+            .call(
+                HttpMethod::DELETE,
+                "/users/user",
+                HeaderMap::new(),
+                &HashMap::new(),
+                None,
+            )
+            .await?;
+        assert_eq!(response.headers()["x-seen"], "1");
+        let request = mock.last_request().expect("request recorded");
+        assert_eq!(request.header("x-correlation-id"), Some("abc"));
+
+        Users::delete(&client, "cached").await?;
+        assert_eq!(mock.requests().len(), 1);
+
+        let err = Users::delete(&client, "blocked").await.unwrap_err();
+        assert!(matches!(err, Error::Interceptor(_)));
+        assert_eq!(mock.requests().len(), 1);
+        Ok(())
+    }
+}
@@ -279,17 +279,32 @@
 //!
 //! NOTE 🎶: for other examples. check out the official docs or sdk of official sdk as a guide to using this sdk.
 
+pub mod bulk;
 pub mod client;
+pub mod config;
 pub mod enumm;
 pub mod enums;
 pub mod error;
 pub mod id;
+pub mod interceptor;
+pub mod migration;
 pub mod models;
+pub mod paginate;
 pub mod permission;
+pub mod pinning;
 pub mod query;
+pub mod query_expr;
 pub mod query_value;
+pub mod rate_limit;
 pub mod realtime;
+pub mod request_options;
+pub mod response_meta;
+pub mod retry;
 pub mod role;
+pub mod schema;
 pub mod services;
+pub mod transaction;
+pub mod transport;
 pub mod upload_progress;
 pub mod utils;
+pub mod wait;
@@ -0,0 +1,1197 @@
+//! # Migration
+//!
+//! Declarative schema migrations. A [`DatabaseSchema`] describes a database
+//! with its collections, attributes and indexes, from code or from a JSON or
+//! TOML file. [`Databases::plan_migration`] reads the live state with
+//! `list_collections`, `list_attributes` and `list_indexes` and returns the
+//! [`MigrationPlan`] that brings the database to the schema, which
+//! [`Databases::migrate`] then applies.
+//!
+//! Each schema has a `version`. Applied versions are recorded as documents of
+//! the bookkeeping collection ([`MIGRATIONS_COLLECTION`] by default), the
+//! version being the document ID, with `version` and `appliedAt` attributes.
+//! Planning a version that was already recorded returns an empty plan.
+//! ```toml
+//! id = "shop"
+//! name = "Shop"
+//! version = "2024-06-01"
+//!
+//! [[collections]]
+//! id = "books"
+//! name = "Books"
+//! attributes = [
+//!     { key = "title", type = "string", size = 255, required = true },
+//!     { key = "rating", type = "integer", min = 0, max = 5, default = 3 },
+//! ]
+//! indexes = [{ key = "title_search", type = "fulltext", attributes = ["title"] }]
+//! ```
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    path::Path,
+};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    client::Client,
+    config::ConfigFormat,
+    error::Error,
+    models::attribute::Attribute,
+    query::Query,
+    schema::{AppwriteCollection, AttributeKind, AttributeSchema, CollectionSchema, IndexSchema},
+    services::server::databases::Databases,
+    wait::{poll, Poll, WaitOptions},
+};
+
+/// Default ID of the collection recording the applied versions.
+pub const MIGRATIONS_COLLECTION: &str = "migrations";
+
+/// Page size used to read the live state.
+const PAGE_SIZE: u64 = 100;
+
+/// Desired state of a database.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseSchema {
+    pub id: String,
+    /// Name of the database, the ID when empty.
+    #[serde(default)]
+    pub name: String,
+    /// Version recorded once the schema is applied. Must be a valid document
+    /// ID: up to 36 of `a-z`, `A-Z`, `0-9`, `.`, `-` and `_`, not starting
+    /// with a special char.
+    pub version: String,
+    #[serde(default)]
+    pub collections: Vec<CollectionSchema>,
+}
+
+impl DatabaseSchema {
+    pub fn new(id: &str, version: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            version: version.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn collection(mut self, collection: CollectionSchema) -> Self {
+        self.collections.push(collection);
+        self
+    }
+
+    /// Add the collection of `T`.
+    pub fn collection_of<T: AppwriteCollection>(self) -> Self {
+        self.collection(T::schema())
+    }
+
+    /// Read a schema from a TOML or JSON file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Err(Error::FilePathNotExist(path.display().to_string()));
+        }
+        let contents = std::fs::read_to_string(path).map_err(|err| Error::ConfigFile {
+            path: path.display().to_string(),
+            message: err.to_string(),
+        })?;
+        Self::parse(&contents, ConfigFormat::from_path(path)).map_err(|err| match err {
+            Error::ConfigFile { message, .. } => Error::ConfigFile {
+                path: path.display().to_string(),
+                message,
+            },
+            err => err,
+        })
+    }
+
+    /// Read a schema from the contents of a schema file.
+    pub fn parse(contents: &str, format: ConfigFormat) -> Result<Self, Error> {
+        match format {
+            ConfigFormat::Toml => toml::from_str(contents).map_err(|err| err.to_string()),
+            ConfigFormat::Json => serde_json::from_str(contents).map_err(|err| err.to_string()),
+        }
+        .map_err(|message| Error::ConfigFile {
+            path: "<memory>".to_string(),
+            message,
+        })
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        let valid_id = self.version.len() <= 36
+            && self
+                .version
+                .starts_with(|c: char| c.is_ascii_alphanumeric())
+            && self
+                .version
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'));
+        if !valid_id {
+            return Err(Error::InvalidSetting {
+                setting: "version".to_string(),
+                message: format!("`{}` is not a valid document ID", self.version),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Settings of [`Databases::plan_migration`] and [`Databases::migrate`].
+#[derive(Debug, Clone)]
+pub struct MigrationOptions {
+    /// Only plan, [`Databases::migrate`] returns the plan without applying it.
+    pub dry_run: bool,
+    /// Delete the collections, attributes and indexes missing from the schema.
+    pub prune: bool,
+    /// Apply plans which delete attributes or collections, and with them data.
+    pub allow_destructive: bool,
+    /// ID of the bookkeeping collection.
+    pub migrations_collection: String,
+    /// Polling of attributes before their indexes are created.
+    pub wait: WaitOptions,
+}
+
+impl Default for MigrationOptions {
+    fn default() -> Self {
+        Self {
+            dry_run: false,
+            prune: false,
+            allow_destructive: false,
+            migrations_collection: MIGRATIONS_COLLECTION.to_string(),
+            wait: WaitOptions::default(),
+        }
+    }
+}
+
+/// One change of a [`MigrationPlan`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum MigrationStep {
+    CreateDatabase {
+        id: String,
+        name: String,
+    },
+    CreateCollection {
+        id: String,
+        name: String,
+        document_security: bool,
+    },
+    UpdateCollection {
+        id: String,
+        name: String,
+        document_security: bool,
+    },
+    DeleteIndex {
+        collection_id: String,
+        key: String,
+    },
+    DeleteAttribute {
+        collection_id: String,
+        key: String,
+    },
+    CreateAttribute {
+        collection_id: String,
+        attribute: AttributeSchema,
+    },
+    UpdateAttribute {
+        collection_id: String,
+        attribute: AttributeSchema,
+    },
+    CreateIndex {
+        collection_id: String,
+        index: IndexSchema,
+    },
+    DeleteCollection {
+        id: String,
+    },
+    RecordVersion {
+        collection_id: String,
+        version: String,
+    },
+}
+
+impl MigrationStep {
+    /// Position of the step in a plan. Collections exist before their
+    /// attributes, relationships are created once every collection has its
+    /// plain attributes, and indexes are dropped before the attributes they
+    /// cover.
+    fn stage(&self) -> u8 {
+        let is_relationship = |attribute: &AttributeSchema| {
+            matches!(attribute.kind, AttributeKind::Relationship { .. })
+        };
+        match self {
+            Self::CreateDatabase { .. } => 0,
+            Self::CreateCollection { .. } | Self::UpdateCollection { .. } => 1,
+            Self::DeleteIndex { .. } => 2,
+            Self::DeleteAttribute { .. } => 3,
+            Self::CreateAttribute { attribute, .. } | Self::UpdateAttribute { attribute, .. } => {
+                if is_relationship(attribute) {
+                    5
+                } else {
+                    4
+                }
+            }
+            Self::CreateIndex { .. } => 6,
+            Self::DeleteCollection { .. } => 7,
+            Self::RecordVersion { .. } => 8,
+        }
+    }
+
+    /// Whether the step deletes documents or their data.
+    pub fn is_destructive(&self) -> bool {
+        matches!(
+            self,
+            Self::DeleteAttribute { .. } | Self::DeleteCollection { .. }
+        )
+    }
+
+    async fn apply(&self, client: &Client, database_id: &str) -> Result<(), Error> {
+        match self {
+            Self::CreateDatabase { id, name } => {
+                let mut args = HashMap::new();
+                args.insert("databaseId".to_string(), json!(id));
+                args.insert("name".to_string(), json!(name));
+                Databases::create(client, args).await?;
+            }
+            Self::CreateCollection {
+                id,
+                name,
+                document_security,
+            } => {
+                let mut args = HashMap::new();
+                args.insert("collectionId".to_string(), json!(id));
+                args.insert("name".to_string(), json!(name));
+                args.insert("documentSecurity".to_string(), json!(document_security));
+                Databases::create_collection(client, database_id, args).await?;
+            }
+            Self::UpdateCollection {
+                id,
+                name,
+                document_security,
+            } => {
+                let mut args = HashMap::new();
+                args.insert("name".to_string(), json!(name));
+                args.insert("documentSecurity".to_string(), json!(document_security));
+                Databases::update_collection(client, database_id, id, args).await?;
+            }
+            Self::DeleteIndex { collection_id, key } => {
+                Databases::delete_index(client, database_id, collection_id, key).await?;
+            }
+            Self::DeleteAttribute { collection_id, key } => {
+                Databases::delete_attribute(client, database_id, collection_id, key).await?;
+            }
+            Self::CreateAttribute {
+                collection_id,
+                attribute,
+            } => attribute.create(client, database_id, collection_id).await?,
+            Self::UpdateAttribute {
+                collection_id,
+                attribute,
+            } => attribute.update(client, database_id, collection_id).await?,
+            Self::CreateIndex {
+                collection_id,
+                index,
+            } => index.create(client, database_id, collection_id).await?,
+            Self::DeleteCollection { id } => {
+                Databases::delete_collection(client, database_id, id).await?;
+            }
+            Self::RecordVersion {
+                collection_id,
+                version,
+            } => {
+                let mut args = HashMap::new();
+                args.insert("documentId".to_string(), json!(version));
+                args.insert(
+                    "data".to_string(),
+                    json!({"version": version, "appliedAt": Utc::now().to_rfc3339()}),
+                );
+                Databases::create_documents(client, database_id, collection_id, args).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn kind_name(kind: &AttributeKind) -> &'static str {
+    match kind {
+        AttributeKind::String { .. } => "string",
+        AttributeKind::Integer { .. } => "integer",
+        AttributeKind::Float { .. } => "float",
+        AttributeKind::Boolean => "boolean",
+        AttributeKind::Datetime => "datetime",
+        AttributeKind::Email => "email",
+        AttributeKind::Url => "url",
+        AttributeKind::Ip => "ip",
+        AttributeKind::Enum { .. } => "enum",
+        AttributeKind::Relationship { .. } => "relationship",
+    }
+}
+
+impl fmt::Display for MigrationStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CreateDatabase { id, .. } => write!(f, "+ create database {}", id),
+            Self::CreateCollection { id, .. } => write!(f, "+ create collection {}", id),
+            Self::UpdateCollection { id, .. } => write!(f, "~ update collection {}", id),
+            Self::DeleteIndex { collection_id, key } => {
+                write!(f, "- delete index {}.{}", collection_id, key)
+            }
+            Self::DeleteAttribute { collection_id, key } => {
+                write!(f, "- delete attribute {}.{}", collection_id, key)
+            }
+            Self::CreateAttribute {
+                collection_id,
+                attribute,
+            } => write!(
+                f,
+                "+ create {} attribute {}.{}",
+                kind_name(&attribute.kind),
+                collection_id,
+                attribute.key
+            ),
+            Self::UpdateAttribute {
+                collection_id,
+                attribute,
+            } => write!(
+                f,
+                "~ update {} attribute {}.{}",
+                kind_name(&attribute.kind),
+                collection_id,
+                attribute.key
+            ),
+            Self::CreateIndex {
+                collection_id,
+                index,
+            } => write!(
+                f,
+                "+ create {} index {}.{} on ({})",
+                index.index_type.as_serialized(),
+                collection_id,
+                index.key,
+                index.attributes.join(", ")
+            ),
+            Self::DeleteCollection { id } => write!(f, "- delete collection {}", id),
+            Self::RecordVersion { version, .. } => write!(f, "+ record version {}", version),
+        }
+    }
+}
+
+/// Ordered changes bringing a database to a [`DatabaseSchema`]. Its
+/// [`Display`](fmt::Display) output is the dry-run report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MigrationPlan {
+    pub database_id: String,
+    pub version: String,
+    /// Whether `version` was recorded before, the plan then has no steps.
+    pub already_applied: bool,
+    pub steps: Vec<MigrationStep>,
+}
+
+impl MigrationPlan {
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Whether any step deletes attributes or collections.
+    pub fn is_destructive(&self) -> bool {
+        self.steps.iter().any(MigrationStep::is_destructive)
+    }
+
+    /// Apply the steps in order. Fails before the first step when the plan is
+    /// destructive and `options.allow_destructive` is not set.
+    ///
+    /// Indexes and the version record are written once the attributes of
+    /// their collection are available, and a recreated attribute once the old
+    /// one is deleted.
+    pub async fn apply(&self, client: &Client, options: &MigrationOptions) -> Result<(), Error> {
+        if self.is_destructive() && !options.allow_destructive {
+            return Err(Error::Migration {
+                version: self.version.clone(),
+                message:
+                    "plan deletes attributes or collections, set `allow_destructive` to apply it"
+                        .to_string(),
+            });
+        }
+        let mut ready = HashSet::new();
+        for step in &self.steps {
+            let result = match self.wait_before(step, client, options, &mut ready).await {
+                Ok(()) => step.apply(client, &self.database_id).await,
+                Err(err) => Err(err),
+            };
+            result.map_err(|err| Error::MigrationStep {
+                step: step.to_string(),
+                source: Box::new(err),
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Wait for the background work `step` depends on. `ready` holds the
+    /// collections whose attributes are known to be available.
+    async fn wait_before<'a>(
+        &self,
+        step: &'a MigrationStep,
+        client: &Client,
+        options: &MigrationOptions,
+        ready: &mut HashSet<&'a str>,
+    ) -> Result<(), Error> {
+        let db = self.database_id.as_str();
+        match step {
+            MigrationStep::CreateIndex { collection_id, .. }
+            | MigrationStep::RecordVersion { collection_id, .. }
+                if !ready.contains(collection_id.as_str()) =>
+            {
+                Databases::wait_for_collection_ready(client, db, collection_id, &options.wait)
+                    .await?;
+                ready.insert(collection_id);
+            }
+            MigrationStep::CreateAttribute {
+                collection_id,
+                attribute,
+            } => {
+                let deleted = self.steps.iter().any(|step| {
+                    matches!(step, MigrationStep::DeleteAttribute { collection_id: c, key }
+                        if c == collection_id && *key == attribute.key)
+                });
+                if deleted {
+                    let resource = format!(
+                        "deletion of attribute `{}.{}`",
+                        collection_id, attribute.key
+                    );
+                    poll(&resource, &options.wait, || async {
+                        match Databases::get_attribute(client, db, collection_id, &attribute.key)
+                            .await
+                        {
+                            Ok(live) => Ok(Poll::Pending(live.status().to_string())),
+                            Err(err) if err.is_not_found() => Ok(Poll::Ready(())),
+                            Err(err) => Err(err),
+                        }
+                    })
+                    .await?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Read the live state of the database and diff it with `schema`.
+    pub async fn new(
+        client: &Client,
+        schema: &DatabaseSchema,
+        options: &MigrationOptions,
+    ) -> Result<Self, Error> {
+        schema.validate()?;
+        let db = schema.id.as_str();
+        let mut steps = Vec::new();
+
+        let exists = match Databases::get(client, db).await {
+            Ok(_) => true,
+            Err(err) if err.is_not_found() => false,
+            Err(err) => return Err(err),
+        };
+        let live_collections = if exists {
+            list_collections(client, db).await?
+        } else {
+            let name = if schema.name.is_empty() {
+                &schema.id
+            } else {
+                &schema.name
+            };
+            steps.push(MigrationStep::CreateDatabase {
+                id: schema.id.clone(),
+                name: name.clone(),
+            });
+            HashMap::new()
+        };
+
+        let migrations = options.migrations_collection.as_str();
+        if live_collections.contains_key(migrations)
+            && applied_versions(client, db, migrations)
+                .await?
+                .contains(&schema.version)
+        {
+            return Ok(Self {
+                database_id: schema.id.clone(),
+                version: schema.version.clone(),
+                already_applied: true,
+                steps,
+            });
+        }
+
+        for collection in &schema.collections {
+            match live_collections.get(&collection.id) {
+                None => {
+                    steps.push(MigrationStep::CreateCollection {
+                        id: collection.id.clone(),
+                        name: collection.name.clone(),
+                        document_security: collection.document_security,
+                    });
+                    steps.extend(collection.attributes.iter().map(|attribute| {
+                        MigrationStep::CreateAttribute {
+                            collection_id: collection.id.clone(),
+                            attribute: attribute.clone(),
+                        }
+                    }));
+                    steps.extend(collection.indexes.iter().map(|index| {
+                        MigrationStep::CreateIndex {
+                            collection_id: collection.id.clone(),
+                            index: index.clone(),
+                        }
+                    }));
+                }
+                Some((name, document_security)) => {
+                    if *name != collection.name
+                        || *document_security != collection.document_security
+                    {
+                        steps.push(MigrationStep::UpdateCollection {
+                            id: collection.id.clone(),
+                            name: collection.name.clone(),
+                            document_security: collection.document_security,
+                        });
+                    }
+                    let attributes = list_attributes(client, db, &collection.id).await?;
+                    let indexes = list_indexes(client, db, &collection.id).await?;
+                    diff_collection(collection, &attributes, &indexes, options, &mut steps);
+                }
+            }
+        }
+
+        if options.prune {
+            let desired: HashSet<_> = schema.collections.iter().map(|c| c.id.as_str()).collect();
+            let mut extra: Vec<_> = live_collections
+                .keys()
+                .filter(|id| !desired.contains(id.as_str()) && id.as_str() != migrations)
+                .collect();
+            extra.sort();
+            steps.extend(
+                extra
+                    .into_iter()
+                    .map(|id| MigrationStep::DeleteCollection { id: id.clone() }),
+            );
+        }
+
+        let live_bookkeeping = if live_collections.contains_key(migrations) {
+            list_attributes(client, db, migrations).await?
+        } else {
+            steps.push(MigrationStep::CreateCollection {
+                id: migrations.to_string(),
+                name: migrations.to_string(),
+                document_security: false,
+            });
+            Vec::new()
+        };
+        steps.extend(
+            bookkeeping_attributes()
+                .into_iter()
+                .filter(|attribute| !live_bookkeeping.iter().any(|a| a.key == attribute.key))
+                .map(|attribute| MigrationStep::CreateAttribute {
+                    collection_id: migrations.to_string(),
+                    attribute,
+                }),
+        );
+        steps.push(MigrationStep::RecordVersion {
+            collection_id: migrations.to_string(),
+            version: schema.version.clone(),
+        });
+
+        steps.sort_by_key(MigrationStep::stage);
+        Ok(Self {
+            database_id: schema.id.clone(),
+            version: schema.version.clone(),
+            already_applied: false,
+            steps,
+        })
+    }
+}
+
+impl fmt::Display for MigrationPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "migration {} of database {}",
+            self.version, self.database_id
+        )?;
+        if self.steps.is_empty() {
+            return write!(f, ": up to date");
+        }
+        for step in &self.steps {
+            write!(f, "\n  {}", step)?;
+        }
+        Ok(())
+    }
+}
+
+/// Diff the attributes and indexes of an existing collection.
+fn diff_collection(
+    collection: &CollectionSchema,
+    live_attributes: &[AttributeSchema],
+    live_indexes: &[IndexSchema],
+    options: &MigrationOptions,
+    steps: &mut Vec<MigrationStep>,
+) {
+    let id = &collection.id;
+    let mut dropped = HashSet::new();
+
+    for attribute in &collection.attributes {
+        let create = MigrationStep::CreateAttribute {
+            collection_id: id.clone(),
+            attribute: attribute.clone(),
+        };
+        match live_attributes
+            .iter()
+            .find(|live| live.key == attribute.key)
+        {
+            None => steps.push(create),
+            Some(live) if needs_recreate(live, attribute) => {
+                dropped.insert(attribute.key.as_str());
+                steps.push(MigrationStep::DeleteAttribute {
+                    collection_id: id.clone(),
+                    key: attribute.key.clone(),
+                });
+                steps.push(create);
+            }
+            Some(live) => {
+                if let Some(attribute) = updated(live, attribute) {
+                    steps.push(MigrationStep::UpdateAttribute {
+                        collection_id: id.clone(),
+                        attribute,
+                    });
+                }
+            }
+        }
+    }
+    if options.prune {
+        for live in live_attributes {
+            if !collection.attributes.iter().any(|a| a.key == live.key) {
+                dropped.insert(live.key.as_str());
+                steps.push(MigrationStep::DeleteAttribute {
+                    collection_id: id.clone(),
+                    key: live.key.clone(),
+                });
+            }
+        }
+    }
+
+    for live in live_indexes {
+        let desired = collection
+            .indexes
+            .iter()
+            .find(|index| index.key == live.key);
+        let covers_dropped = live.attributes.iter().any(|a| dropped.contains(a.as_str()));
+        let delete = match desired {
+            Some(index) => covers_dropped || index_changed(live, index),
+            None => covers_dropped || options.prune,
+        };
+        if delete {
+            steps.push(MigrationStep::DeleteIndex {
+                collection_id: id.clone(),
+                key: live.key.clone(),
+            });
+        }
+    }
+    for index in &collection.indexes {
+        let recreate = match live_indexes.iter().find(|live| live.key == index.key) {
+            None => true,
+            Some(live) => {
+                live.attributes.iter().any(|a| dropped.contains(a.as_str()))
+                    || index_changed(live, index)
+            }
+        };
+        if recreate {
+            steps.push(MigrationStep::CreateIndex {
+                collection_id: id.clone(),
+                index: index.clone(),
+            });
+        }
+    }
+}
+
+/// Whether the change can only be made by deleting and creating the
+/// attribute again.
+fn needs_recreate(live: &AttributeSchema, desired: &AttributeSchema) -> bool {
+    if std::mem::discriminant(&live.kind) != std::mem::discriminant(&desired.kind)
+        || live.array != desired.array
+    {
+        return true;
+    }
+    match (&live.kind, &desired.kind) {
+        (
+            AttributeKind::String { encrypt, .. },
+            AttributeKind::String {
+                encrypt: desired_encrypt,
+                ..
+            },
+        ) => encrypt != desired_encrypt,
+        (
+            AttributeKind::Relationship {
+                related_collection,
+                relation_type,
+                two_way,
+                two_way_key,
+                ..
+            },
+            AttributeKind::Relationship {
+                related_collection: desired_collection,
+                relation_type: desired_type,
+                two_way: desired_two_way,
+                two_way_key: desired_key,
+                ..
+            },
+        ) => {
+            related_collection != desired_collection
+                || relation_type != desired_type
+                || two_way != desired_two_way
+                || (desired_key.is_some() && two_way_key != desired_key)
+        }
+        _ => false,
+    }
+}
+
+/// Desired attribute to update `live` with, `None` when they match. Unset
+/// bounds of numeric attributes keep their live value.
+fn updated(live: &AttributeSchema, desired: &AttributeSchema) -> Option<AttributeSchema> {
+    let mut attribute = desired.clone();
+    match (&live.kind, &mut attribute.kind) {
+        (
+            AttributeKind::Integer { min, max },
+            AttributeKind::Integer {
+                min: dmin,
+                max: dmax,
+            },
+        ) => {
+            *dmin = dmin.or(*min);
+            *dmax = dmax.or(*max);
+        }
+        (
+            AttributeKind::Float { min, max },
+            AttributeKind::Float {
+                min: dmin,
+                max: dmax,
+            },
+        ) => {
+            *dmin = dmin.or(*min);
+            *dmax = dmax.or(*max);
+        }
+        _ => {}
+    }
+    let same_default = match (&live.default, &attribute.default) {
+        (Some(Value::Number(a)), Some(Value::Number(b))) => a.as_f64() == b.as_f64(),
+        (a, b) => a == b,
+    };
+    if live.kind == attribute.kind && live.required == attribute.required && same_default {
+        None
+    } else {
+        Some(attribute)
+    }
+}
+
+fn index_changed(live: &IndexSchema, desired: &IndexSchema) -> bool {
+    live.index_type != desired.index_type
+        || live.attributes != desired.attributes
+        || (!desired.orders.is_empty() && live.orders != desired.orders)
+}
+
+fn page_args(offset: u64) -> HashMap<String, Value> {
+    let mut args = HashMap::new();
+    args.insert(
+        "queries".to_string(),
+        json!([Query::limit(PAGE_SIZE.into()), Query::offset(offset.into())]),
+    );
+    args
+}
+
+/// Name and document security of every collection, by ID.
+async fn list_collections(
+    client: &Client,
+    database_id: &str,
+) -> Result<HashMap<String, (String, bool)>, Error> {
+    let mut collections = HashMap::new();
+    loop {
+        let page =
+            Databases::list_collections(client, database_id, page_args(collections.len() as u64))
+                .await?;
+        let done = page.collections.is_empty();
+        collections.extend(
+            page.collections
+                .into_iter()
+                .map(|c| (c.id, (c.name, c.document_security))),
+        );
+        if done || collections.len() as u64 >= page.total {
+            return Ok(collections);
+        }
+    }
+}
+
+/// Attributes of the collection, without the child side of two-way
+/// relationships, which is managed from the parent collection.
+async fn list_attributes(
+    client: &Client,
+    database_id: &str,
+    collection_id: &str,
+) -> Result<Vec<AttributeSchema>, Error> {
+    let (mut attributes, mut offset) = (Vec::new(), 0);
+    loop {
+        let page =
+            Databases::list_attributes(client, database_id, collection_id, page_args(offset))
+                .await?;
+        offset += page.attributes.len() as u64;
+        let done = page.attributes.is_empty() || offset >= page.total;
+        attributes.extend(
+            page.attributes
+                .iter()
+                .filter(|attribute| {
+                    !matches!(attribute, Attribute::Relationship(a) if a.side == "child")
+                })
+                .filter_map(AttributeSchema::from_attribute),
+        );
+        if done {
+            return Ok(attributes);
+        }
+    }
+}
+
+async fn list_indexes(
+    client: &Client,
+    database_id: &str,
+    collection_id: &str,
+) -> Result<Vec<IndexSchema>, Error> {
+    let mut indexes = Vec::new();
+    loop {
+        let page = Databases::list_indexes(
+            client,
+            database_id,
+            collection_id,
+            page_args(indexes.len() as u64),
+        )
+        .await?;
+        let done = page.indexes.is_empty();
+        for index in page.indexes {
+            let strings = |values: Vec<Value>| {
+                values
+                    .into_iter()
+                    .filter_map(|v| v.as_str().map(str::to_string))
+                    .collect()
+            };
+            indexes.push(IndexSchema {
+                key: index.key,
+                index_type: serde_json::from_value(json!(index.index_type)).unwrap_or_default(),
+                attributes: strings(index.attributes),
+                orders: strings(index.orders.unwrap_or_default()),
+            });
+        }
+        if done || indexes.len() as u64 >= page.total {
+            return Ok(indexes);
+        }
+    }
+}
+
+/// Attributes of the bookkeeping collection.
+fn bookkeeping_attributes() -> [AttributeSchema; 2] {
+    let attribute = |key: &str, kind| AttributeSchema {
+        key: key.to_string(),
+        kind,
+        required: true,
+        default: None,
+        array: false,
+    };
+    [
+        attribute(
+            "version",
+            AttributeKind::String {
+                size: 255,
+                encrypt: false,
+            },
+        ),
+        attribute("appliedAt", AttributeKind::Datetime),
+    ]
+}
+
+async fn applied_versions(
+    client: &Client,
+    database_id: &str,
+    collection_id: &str,
+) -> Result<HashSet<String>, Error> {
+    let mut versions = HashSet::new();
+    let mut offset = 0;
+    loop {
+        let page = Databases::list_documents(client, database_id, collection_id, page_args(offset))
+            .await?;
+        offset += page.documents.len() as u64;
+        let done = page.documents.is_empty() || offset >= page.total;
+        versions.extend(page.documents.into_iter().map(|document| document.id));
+        if done {
+            return Ok(versions);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        enumm::HttpMethod,
+        enums::index_type::IndexType,
+        transport::{MockResponse, MockTransport},
+    };
+
+    fn books() -> CollectionSchema {
+        CollectionSchema {
+            id: "books".into(),
+            name: "Books".into(),
+            document_security: false,
+            attributes: vec![
+                AttributeSchema {
+                    key: "title".into(),
+                    kind: AttributeKind::String {
+                        size: 255,
+                        encrypt: false,
+                    },
+                    required: true,
+                    default: None,
+                    array: false,
+                },
+                AttributeSchema {
+                    key: "rating".into(),
+                    kind: AttributeKind::Integer {
+                        min: Some(0),
+                        max: Some(5),
+                    },
+                    required: false,
+                    default: Some(json!(3)),
+                    array: false,
+                },
+            ],
+            indexes: vec![IndexSchema {
+                key: "title_search".into(),
+                index_type: IndexType::Fulltext,
+                attributes: vec!["title".into()],
+                orders: vec![],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_parse_schema() -> Result<(), Error> {
+        let toml = r#"
+            id = "shop"
+            version = "2024-06-01"
+
+            [[collections]]
+            id = "books"
+            name = "Books"
+            attributes = [
+                { key = "title", type = "string", size = 255, required = true },
+                { key = "rating", type = "integer", min = 0, max = 5, default = 3 },
+            ]
+            indexes = [{ key = "title_search", type = "fulltext", attributes = ["title"] }]
+        "#;
+        let schema = DatabaseSchema::parse(toml, ConfigFormat::Toml)?;
+        assert_eq!(
+            schema,
+            DatabaseSchema::new("shop", "2024-06-01").collection(books())
+        );
+
+        let json = serde_json::to_string(&schema).unwrap();
+        assert_eq!(DatabaseSchema::parse(&json, ConfigFormat::Json)?, schema);
+
+        let err = DatabaseSchema::new("shop", "v 1").validate().unwrap_err();
+        assert!(matches!(err, Error::InvalidSetting { setting, .. } if setting == "version"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_plan_migration() -> Result<(), Error> {
+        let (mock, client) = MockTransport::client()?;
+        mock.on(
+            HttpMethod::GET,
+            "/databases/shop",
+            MockResponse::json(200, json!({"$id": "shop", "name": "Shop", "$createdAt": "", "$updatedAt": "", "enabled": true})),
+        )
+        .on(
+            HttpMethod::GET,
+            "/databases/shop/collections",
+            MockResponse::json(
+                200,
+                json!({"total": 2, "collections": [
+                    {"$id": "books", "$createdAt": "", "$updatedAt": "", "$permissions": [], "databaseId": "shop",
+                     "name": "Books", "enabled": true, "documentSecurity": false, "attributes": [], "indexes": []},
+                    {"$id": "legacy", "$createdAt": "", "$updatedAt": "", "$permissions": [], "databaseId": "shop",
+                     "name": "Legacy", "enabled": true, "documentSecurity": false, "attributes": [], "indexes": []},
+                ]}),
+            ),
+        )
+        .on(
+            HttpMethod::GET,
+            "/databases/shop/collections/books/attributes",
+            MockResponse::json(
+                200,
+                json!({"total": 3, "attributes": [
+                    {"key": "title", "type": "string", "status": "available", "error": "", "required": true, "array": false, "size": 100},
+                    {"key": "rating", "type": "string", "status": "available", "error": "", "required": false, "array": false, "size": 1},
+                    {"key": "shelf", "type": "relationship", "status": "available", "error": "", "required": false, "array": false,
+                     "relatedCollection": "shelves", "relationType": "manyToOne", "twoWay": true,
+                     "twoWayKey": "books", "onDelete": "setNull", "side": "child"},
+                ]}),
+            ),
+        )
+        .on(
+            HttpMethod::GET,
+            "/databases/shop/collections/books/indexes",
+            MockResponse::json(
+                200,
+                json!({"total": 1, "indexes": [
+                    {"key": "rating_key", "type": "key", "status": "available", "error": "",
+                     "attributes": ["rating"], "orders": ["ASC"]},
+                ]}),
+            ),
+        );
+
+        let schema = DatabaseSchema::new("shop", "2024-06-01").collection(books());
+        let options = MigrationOptions {
+            prune: true,
+            ..Default::default()
+        };
+        let plan = Databases::plan_migration(&client, &schema, &options).await?;
+        assert_eq!(
+            plan.to_string(),
+            "migration 2024-06-01 of database shop\
+            \n  + create collection migrations\
+            \n  - delete index books.rating_key\
+            \n  - delete attribute books.rating\
+            \n  ~ update string attribute books.title\
+            \n  + create integer attribute books.rating\
+            \n  + create string attribute migrations.version\
+            \n  + create datetime attribute migrations.appliedAt\
+            \n  + create fulltext index books.title_search on (title)\
+            \n  - delete collection legacy\
+            \n  + record version 2024-06-01"
+        );
+        assert!(plan.is_destructive());
+
+        let err = plan.apply(&client, &options).await.unwrap_err();
+        assert!(matches!(err, Error::Migration { .. }));
+
+        let requests = mock.requests().len();
+        let dry_run = Databases::migrate(
+            &client,
+            &schema,
+            &MigrationOptions {
+                dry_run: true,
+                ..options
+            },
+        )
+        .await?;
+        assert_eq!(dry_run, plan);
+        assert!(mock
+            .requests()
+            .iter()
+            .skip(requests)
+            .all(|request| request.method == HttpMethod::GET));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_migrate_records_version() -> Result<(), Error> {
+        let (mock, client) = MockTransport::client()?;
+        let migrations = "/databases/shop/collections/migrations";
+        let collection = json!({"$id": "migrations", "$createdAt": "", "$updatedAt": "",
+        "$permissions": [], "databaseId": "shop", "name": "migrations", "enabled": true,
+        "documentSecurity": false, "indexes": [], "attributes": [
+            {"key": "version", "type": "string", "status": "available", "error": "",
+             "required": true, "size": 255},
+            {"key": "appliedAt", "type": "datetime", "format": "datetime",
+             "status": "available", "error": "", "required": true},
+        ]});
+        mock.on(
+            HttpMethod::POST,
+            "/databases",
+            MockResponse::json(
+                201,
+                json!({"$id": "shop", "name": "shop", "$createdAt": "",
+                                           "$updatedAt": "", "enabled": true}),
+            ),
+        )
+        .on(
+            HttpMethod::POST,
+            "/databases/shop/collections",
+            MockResponse::json(201, collection.clone()),
+        )
+        .on(
+            HttpMethod::POST,
+            &format!("{}/attributes/string", migrations),
+            MockResponse::json(202, collection["attributes"][0].clone()),
+        )
+        .on(
+            HttpMethod::POST,
+            &format!("{}/attributes/datetime", migrations),
+            MockResponse::json(202, collection["attributes"][1].clone()),
+        )
+        .on(
+            HttpMethod::GET,
+            migrations,
+            MockResponse::json(200, collection),
+        )
+        .on(
+            HttpMethod::POST,
+            &format!("{}/documents", migrations),
+            MockResponse::json(
+                201,
+                json!({"$id": "v1", "$collectionId": "migrations",
+                "$databaseId": "shop", "$createdAt": "", "$updatedAt": "", "$permissions": []}),
+            ),
+        );
+
+        let schema = DatabaseSchema::new("shop", "v1");
+        let plan = Databases::migrate(&client, &schema, &MigrationOptions::default()).await?;
+        assert_eq!(plan.steps.len(), 5);
+
+        let requests = mock.requests();
+        let record = requests.last().unwrap();
+        assert_eq!(record.path, format!("{}/documents", migrations));
+        let body = record.body.as_ref().unwrap();
+        assert_eq!(body["documentId"], json!("v1"));
+        assert_eq!(body["data"]["version"], json!("v1"));
+        assert!(
+            chrono::DateTime::parse_from_rfc3339(body["data"]["appliedAt"].as_str().unwrap())
+                .is_ok()
+        );
+
+        let (mock, client) = MockTransport::client()?;
+        mock.on(
+            HttpMethod::GET,
+            "/databases/shop",
+            MockResponse::json(
+                200,
+                json!({"$id": "shop", "name": "shop", "$createdAt": "",
+                                           "$updatedAt": "", "enabled": true}),
+            ),
+        )
+        .on(
+            HttpMethod::GET,
+            "/databases/shop/collections",
+            MockResponse::json(
+                200,
+                json!({"total": 1, "collections": [
+                {"$id": "migrations", "$createdAt": "", "$updatedAt": "", "$permissions": [],
+                 "databaseId": "shop", "name": "migrations", "enabled": true,
+                 "documentSecurity": false, "attributes": [], "indexes": []}]}),
+            ),
+        )
+        .on(
+            HttpMethod::GET,
+            &format!("{}/documents", migrations),
+            MockResponse::json(
+                200,
+                json!({"total": 1, "documents": [
+                {"$id": "v1", "$collectionId": "migrations", "$databaseId": "shop",
+                 "$createdAt": "", "$updatedAt": "", "$permissions": []}]}),
+            ),
+        );
+        let plan =
+            Databases::plan_migration(&client, &schema, &MigrationOptions::default()).await?;
+        assert!(plan.already_applied);
+        assert!(plan.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,126 @@
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+use super::{
+    attribute_boolean::AttributeBoolean, attribute_datetime::AttributeDateTime,
+    attribute_email::AttributeEmail, attribute_enum::AttributeEnum,
+    attribute_float::AttributeFloat, attribute_integer::AttributeInteger,
+    attribute_ip::AttributeIp, attribute_relationship::AttributeRelationship,
+    attribute_string::AttributeString, attribute_url::AttributeUrl,
+};
+
+/// Attribute
+///
+/// Attribute of any type, read from its `type` and `format` fields. Types
+/// unknown to this crate, and known types whose payload doesn't match their
+/// struct, are kept as [`Attribute::Unknown`].
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum Attribute {
+    Boolean(AttributeBoolean),
+    Integer(AttributeInteger),
+    Float(AttributeFloat),
+    String(AttributeString),
+    Email(AttributeEmail),
+    Enum(AttributeEnum),
+    Ip(AttributeIp),
+    Url(AttributeUrl),
+    Datetime(AttributeDateTime),
+    Relationship(AttributeRelationship),
+    Unknown(Value),
+}
+
+impl<'de> Deserialize<'de> for Attribute {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        let field = |name: &str| value.get(name).and_then(Value::as_str).unwrap_or_default();
+        let typed = match (field("type"), field("format")) {
+            ("boolean", _) => serde_json::from_value(value.clone()).map(Self::Boolean),
+            ("integer", _) => serde_json::from_value(value.clone()).map(Self::Integer),
+            ("double", _) => serde_json::from_value(value.clone()).map(Self::Float),
+            ("string", "email") => serde_json::from_value(value.clone()).map(Self::Email),
+            ("string", "enum") => serde_json::from_value(value.clone()).map(Self::Enum),
+            ("string", "ip") => serde_json::from_value(value.clone()).map(Self::Ip),
+            ("string", "url") => serde_json::from_value(value.clone()).map(Self::Url),
+            ("string", _) => serde_json::from_value(value.clone()).map(Self::String),
+            ("datetime", _) => serde_json::from_value(value.clone()).map(Self::Datetime),
+            ("relationship", _) => serde_json::from_value(value.clone()).map(Self::Relationship),
+            _ => return Ok(Self::Unknown(value)),
+        };
+        Ok(typed.unwrap_or(Self::Unknown(value)))
+    }
+}
+
+/// Field shared by every attribute type.
+macro_rules! common_field {
+    ($attribute:expr, $field:ident) => {
+        match $attribute {
+            Attribute::Boolean(a) => a.$field.as_str(),
+            Attribute::Integer(a) => a.$field.as_str(),
+            Attribute::Float(a) => a.$field.as_str(),
+            Attribute::String(a) => a.$field.as_str(),
+            Attribute::Email(a) => a.$field.as_str(),
+            Attribute::Enum(a) => a.$field.as_str(),
+            Attribute::Ip(a) => a.$field.as_str(),
+            Attribute::Url(a) => a.$field.as_str(),
+            Attribute::Datetime(a) => a.$field.as_str(),
+            Attribute::Relationship(a) => a.$field.as_str(),
+            Attribute::Unknown(value) => value
+                .get(stringify!($field))
+                .and_then(Value::as_str)
+                .unwrap_or_default(),
+        }
+    };
+}
+
+impl Attribute {
+    /// Attribute Key.
+    pub fn key(&self) -> &str {
+        common_field!(self, key)
+    }
+
+    /// Attribute status. Possible values: `available`, `processing`, `deleting`, `stuck`, or `failed`
+    pub fn status(&self) -> &str {
+        common_field!(self, status)
+    }
+
+    /// Error message. Displays error generated on failure of creating or deleting an attribute.
+    pub fn error(&self) -> &str {
+        common_field!(self, error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_attribute_dispatch() {
+        let list: Vec<Attribute> = serde_json::from_value(json!([
+            {"key": "contact", "type": "string", "format": "email", "status": "available",
+             "error": "", "required": false, "array": false, "default": null},
+            {"key": "rating", "type": "integer", "status": "processing", "error": "",
+             "required": false, "min": -10, "max": 10, "default": 3},
+            {"key": "location", "type": "point", "status": "available", "error": ""},
+            {"key": "pages", "type": "integer", "status": "available", "error": "",
+             "required": false, "min": "one"},
+        ]))
+        .unwrap();
+
+        assert!(matches!(&list[0], Attribute::Email(a) if a.key == "contact"));
+        assert!(
+            matches!(&list[1], Attribute::Integer(a) if a.min == Some(-10) && a.xdefault == Some(3))
+        );
+        assert!(matches!(&list[2], Attribute::Unknown(_)));
+        assert_eq!(list[1].status(), "processing");
+        assert_eq!(list[2].key(), "location");
+        assert!(matches!(&list[3], Attribute::Unknown(value) if value["min"] == "one"));
+        assert_eq!(list[3].key(), "pages");
+        assert_eq!(
+            serde_json::to_value(&list[2]).unwrap()["type"],
+            json!("point")
+        );
+    }
+}
@@ -17,11 +17,13 @@ pub struct AttributeBoolean {
     pub error: String,
 
     /// Is attribute required?
+    #[serde(rename = "required")]
     pub xrequired: Option<bool>,
 
     /// Is attribute an array?
     pub array: Option<bool>,
 
     /// Default value for attribute when not provided. Cannot be set when attribute is required.
-    pub xdefault: Option<String>,
+    #[serde(rename = "default")]
+    pub xdefault: Option<bool>,
 }
@@ -18,6 +18,7 @@ pub struct AttributeEnum {
     pub error: String,
 
     /// Is attribute required?
+    #[serde(rename = "required")]
     pub xrequired: bool,
 
     /// Is attribute an array?
@@ -30,5 +31,6 @@ pub struct AttributeEnum {
     pub format: String,
 
     /// Default value for attribute when not provided. Cannot be set when attribute is required.
+    #[serde(rename = "default")]
     pub xdefault: Option<String>,
 }
@@ -17,6 +17,7 @@ pub struct AttributeFloat {
     pub error: String,
 
     /// Is attribute required?
+    #[serde(rename = "required")]
     pub xrequired: bool,
 
     /// Is attribute an array?
@@ -29,5 +30,6 @@ pub struct AttributeFloat {
     pub max: Option<f64>,
 
     /// Default value for attribute when not provided. Cannot be set when attribute is required.
+    #[serde(rename = "default")]
     pub xdefault: Option<f64>,
 }
@@ -17,17 +17,19 @@ pub struct AttributeInteger {
     pub error: String,
 
     /// Is attribute required?
+    #[serde(rename = "required")]
     pub xrequired: bool,
 
     /// Is attribute an array?
     pub array: Option<bool>,
 
     /// Minimum value to enforce for new documents.
-    pub min: Option<u64>,
+    pub min: Option<i64>,
 
     /// Maximum value to enforce for new documents.
-    pub max: Option<u64>,
+    pub max: Option<i64>,
 
     /// Default value for attribute when not provided. Cannot be set when attribute is required.
-    pub xdefault: Option<u64>,
+    #[serde(rename = "default")]
+    pub xdefault: Option<i64>,
 }
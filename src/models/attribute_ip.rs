@@ -17,6 +17,7 @@ pub struct AttributeIp {
     pub error: String,
 
     /// Is attribute required?
+    #[serde(rename = "required")]
     pub xrequired: bool,
 
     /// Is attribute an array?
@@ -26,5 +27,6 @@ pub struct AttributeIp {
     pub format: String,
 
     /// Default value for attribute when not provided. Cannot be set when attribute is required.
+    #[serde(rename = "default")]
     pub xdefault: Option<String>,
 }
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+
+use super::attribute::Attribute;
 
 /// Attributes List
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
@@ -7,5 +8,5 @@ pub struct AttributeList {
     /// Total number of attributes in the given collection.
     pub total: u64,
     /// List of attributes.
-    pub attributes: Vec<Value>,
+    pub attributes: Vec<Attribute>,
 }
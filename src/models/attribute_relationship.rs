@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-/// AttributeString
+/// AttributeRelationship
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
 pub struct AttributeRelationship {
     /// Attribute Key.
@@ -17,6 +17,7 @@ pub struct AttributeRelationship {
     pub error: String,
 
     /// Is attribute required?
+    #[serde(rename = "required")]
     pub xrequired: bool,
 
     /// Is attribute an array?
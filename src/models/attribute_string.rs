@@ -17,6 +17,7 @@ pub struct AttributeString {
     pub error: String,
 
     /// Is attribute required?
+    #[serde(rename = "required")]
     pub xrequired: bool,
 
     /// Is attribute an array?
@@ -26,5 +27,10 @@ pub struct AttributeString {
     pub size: u64,
 
     /// Default value for attribute when not provided. Cannot be set when attribute is required.
+    #[serde(rename = "default")]
     pub xdefault: Option<String>,
+
+    /// Is the attribute encrypted?
+    #[serde(default)]
+    pub encrypt: Option<bool>,
 }
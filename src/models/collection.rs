@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use super::index::Index;
+use super::{attribute::Attribute, index::Index};
 
 /// Collection
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
@@ -37,7 +37,7 @@ pub struct Collection {
     pub document_security: bool,
 
     /// Collection attributes.
-    pub attributes: Vec<Value>,
+    pub attributes: Vec<Attribute>,
 
     /// Collection indexes.
     pub indexes: Vec<Index>,
@@ -0,0 +1,89 @@
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+use super::{
+    column_boolean::ColumnBoolean, column_datetime::ColumnDateTime, column_email::ColumnEmail,
+    column_enum::ColumnEnum, column_float::ColumnFloat, column_integer::ColumnInteger,
+    column_ip::ColumnIp, column_relationship::ColumnRelationship, column_string::ColumnString,
+    column_url::ColumnUrl,
+};
+
+/// Column
+///
+/// Column of any type, read from its `type` and `format` fields. Types
+/// unknown to this crate are kept as [`Column::Unknown`].
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum Column {
+    Boolean(ColumnBoolean),
+    Integer(ColumnInteger),
+    Float(ColumnFloat),
+    String(ColumnString),
+    Email(ColumnEmail),
+    Enum(ColumnEnum),
+    Ip(ColumnIp),
+    Url(ColumnUrl),
+    Datetime(ColumnDateTime),
+    Relationship(ColumnRelationship),
+    Unknown(Value),
+}
+
+impl<'de> Deserialize<'de> for Column {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        let field = |name: &str| value.get(name).and_then(Value::as_str).unwrap_or_default();
+        let typed = match (field("type"), field("format")) {
+            ("boolean", _) => serde_json::from_value(value.clone()).map(Self::Boolean),
+            ("integer", _) => serde_json::from_value(value.clone()).map(Self::Integer),
+            ("double", _) => serde_json::from_value(value.clone()).map(Self::Float),
+            ("string", "email") => serde_json::from_value(value.clone()).map(Self::Email),
+            ("string", "enum") => serde_json::from_value(value.clone()).map(Self::Enum),
+            ("string", "ip") => serde_json::from_value(value.clone()).map(Self::Ip),
+            ("string", "url") => serde_json::from_value(value.clone()).map(Self::Url),
+            ("string", _) => serde_json::from_value(value.clone()).map(Self::String),
+            ("datetime", _) => serde_json::from_value(value.clone()).map(Self::Datetime),
+            ("relationship", _) => serde_json::from_value(value.clone()).map(Self::Relationship),
+            _ => return Ok(Self::Unknown(value)),
+        };
+        typed.map_err(serde::de::Error::custom)
+    }
+}
+
+/// Field shared by every column type.
+macro_rules! common_field {
+    ($column:expr, $field:ident) => {
+        match $column {
+            Column::Boolean(a) => a.$field.as_str(),
+            Column::Integer(a) => a.$field.as_str(),
+            Column::Float(a) => a.$field.as_str(),
+            Column::String(a) => a.$field.as_str(),
+            Column::Email(a) => a.$field.as_str(),
+            Column::Enum(a) => a.$field.as_str(),
+            Column::Ip(a) => a.$field.as_str(),
+            Column::Url(a) => a.$field.as_str(),
+            Column::Datetime(a) => a.$field.as_str(),
+            Column::Relationship(a) => a.$field.as_str(),
+            Column::Unknown(value) => value
+                .get(stringify!($field))
+                .and_then(Value::as_str)
+                .unwrap_or_default(),
+        }
+    };
+}
+
+impl Column {
+    /// Column Key.
+    pub fn key(&self) -> &str {
+        common_field!(self, key)
+    }
+
+    /// Column status. Possible values: `available`, `processing`, `deleting`, `stuck`, or `failed`
+    pub fn status(&self) -> &str {
+        common_field!(self, status)
+    }
+
+    /// Error message. Displays error generated on failure of creating or deleting a column.
+    pub fn error(&self) -> &str {
+        common_field!(self, error)
+    }
+}
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// ColumnEnum
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ColumnEnum {
+    /// Column Key.
+    pub key: String,
+
+    /// Column type.
+    #[serde(rename = "type")]
+    pub column_type: String,
+
+    /// Column status. Possible values: `available`, `processing`, `deleting`, `stuck`, or `failed`
+    pub status: String,
+
+    /// Error message. Displays error generated on failure of creating or deleting a column.
+    pub error: String,
+
+    /// Is column required?
+    #[serde(rename = "required")]
+    pub xrequired: bool,
+
+    /// Is column an array?
+    pub array: Option<bool>,
+
+    /// Array of elements in enumerated type.
+    pub elements: Vec<Value>,
+
+    /// String format.
+    pub format: String,
+
+    /// Default value for column when not provided. Cannot be set when column is required.
+    #[serde(rename = "default")]
+    pub xdefault: Option<String>,
+}
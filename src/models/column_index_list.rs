@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+use super::column_index::ColumnIndex;
+
+/// Column Indexes List
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ColumnIndexList {
+    /// Total number of indexes that matched your query.
+    pub total: u64,
+    /// List of indexes.
+    pub indexes: Vec<ColumnIndex>,
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+use super::column::Column;
+
+/// Columns List
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ColumnList {
+    /// Total number of columns in the given table.
+    pub total: u64,
+    /// List of columns.
+    pub columns: Vec<Column>,
+}
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+
+/// ColumnRelationship
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ColumnRelationship {
+    /// Column Key.
+    pub key: String,
+
+    /// Column type.
+    #[serde(rename = "type")]
+    pub column_type: String,
+
+    /// Column status. Possible values: `available`, `processing`, `deleting`, `stuck`, or `failed`
+    pub status: String,
+
+    /// Error message. Displays error generated on failure of creating or deleting a column.
+    pub error: String,
+
+    /// Is column required?
+    #[serde(rename = "required")]
+    pub xrequired: bool,
+
+    /// Is column an array?
+    pub array: Option<bool>,
+
+    /// The ID of the related table.
+    #[serde(rename = "relatedTable")]
+    pub related_table: String,
+
+    /// The type of the relationship.
+    #[serde(rename = "relationType")]
+    pub relation_type: String,
+
+    /// Is the relationship two-way?
+    #[serde(rename = "twoWay")]
+    pub two_way: bool,
+
+    /// The key of the two-way relationship.
+    #[serde(rename = "twoWayKey")]
+    pub two_way_key: String,
+
+    /// How deleting the parent row will propagate to child rows.
+    #[serde(rename = "onDelete")]
+    pub on_delete: String,
+
+    /// Whether this is the parent or child side of the relationship
+    pub side: String,
+}
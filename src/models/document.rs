@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 
 /// Document
@@ -10,6 +10,16 @@ pub struct Document {
     #[serde(rename = "$id")]
     pub id: String,
 
+    /// Document automatically incrementing ID, `None` on servers that
+    /// predate it.
+    #[serde(
+        rename = "$sequence",
+        default,
+        deserialize_with = "deserialize_sequence",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub sequence: Option<u64>,
+
     /// Collection ID.
     #[serde(rename = "$collectionId")]
     pub collection_id: String,
@@ -33,3 +43,21 @@ pub struct Document {
     #[serde(flatten)]
     pub data: HashMap<String, Value>,
 }
+
+/// `$sequence` is a number on some server versions and a numeric string on
+/// others.
+pub(crate) fn deserialize_sequence<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<u64>, D::Error> {
+    match Option::<Value>::deserialize(deserializer)? {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::Number(number)) => number
+            .as_u64()
+            .map(Some)
+            .ok_or_else(|| serde::de::Error::custom("invalid $sequence")),
+        Some(Value::String(sequence)) => {
+            sequence.parse().map(Some).map_err(serde::de::Error::custom)
+        }
+        Some(_) => Err(serde::de::Error::custom("invalid $sequence")),
+    }
+}
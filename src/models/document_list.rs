@@ -3,10 +3,13 @@ use serde::{Deserialize, Serialize};
 use super::document::Document;
 
 /// Document List
+///
+/// `T` is [`Document`] unless the documents were read as
+/// [`TypedDocument`](super::typed_document::TypedDocument)s.
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
-pub struct DocumentList {
+pub struct DocumentList<T = Document> {
     /// Total number of document documents that matched your query.
     pub total: u64,
     /// List of document.
-    pub documents: Vec<Document>,
+    pub documents: Vec<T>,
 }
@@ -8,5 +8,6 @@ pub struct MembershipList {
     /// Total number of memberships documents that matched your query.
     pub total: u64,
     /// List of memberships.
+    #[serde(rename = "memberships")]
     pub sessions: Vec<Membership>,
 }
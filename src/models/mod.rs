@@ -7,6 +7,7 @@ pub mod algo_phpass;
 pub mod algo_scrypt;
 pub mod algo_scrypt_modified;
 pub mod algo_sha;
+pub mod attribute;
 pub mod attribute_boolean;
 pub mod attribute_datetime;
 pub mod attribute_email;
@@ -23,6 +24,20 @@ pub mod bucket_list;
 pub mod build;
 pub mod collection;
 pub mod collection_list;
+pub mod column;
+pub mod column_boolean;
+pub mod column_datetime;
+pub mod column_email;
+pub mod column_enum;
+pub mod column_float;
+pub mod column_index;
+pub mod column_index_list;
+pub mod column_integer;
+pub mod column_ip;
+pub mod column_list;
+pub mod column_relationship;
+pub mod column_string;
+pub mod column_url;
 pub mod continent;
 pub mod continent_list;
 pub mod country;
@@ -73,6 +88,8 @@ pub mod phone_list;
 pub mod preferences;
 pub mod provider;
 pub mod provider_list;
+pub mod row;
+pub mod row_list;
 pub mod runtime;
 pub mod runtime_list;
 pub mod session;
@@ -80,6 +97,8 @@ pub mod session_list;
 pub mod specification;
 pub mod subscriber;
 pub mod subscriber_list;
+pub mod table;
+pub mod table_list;
 pub mod target;
 pub mod target_list;
 pub mod team;
@@ -87,12 +106,17 @@ pub mod team_list;
 pub mod token;
 pub mod topic;
 pub mod topic_list;
+pub mod transaction;
+pub mod transaction_list;
+pub mod typed_document;
+pub mod typed_row;
 pub mod user;
 pub mod user_list;
 pub mod variable;
 pub mod variable_list;
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[allow(clippy::large_enum_variant)]
 pub enum UploadType {
     File(self::file::File),
     Deployment(self::deployment::Deployment),
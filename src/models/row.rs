@@ -0,0 +1,47 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::document::deserialize_sequence;
+
+/// Row
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct Row {
+    /// Row ID.
+    #[serde(rename = "$id")]
+    pub id: String,
+
+    /// Row automatically incrementing ID, `None` on servers that
+    /// predate it.
+    #[serde(
+        rename = "$sequence",
+        default,
+        deserialize_with = "deserialize_sequence",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub sequence: Option<u64>,
+
+    /// Table ID.
+    #[serde(rename = "$tableId")]
+    pub table_id: String,
+
+    /// Database ID.
+    #[serde(rename = "$databaseId")]
+    pub database_id: String,
+
+    /// Row creation date in ISO 8601 format.
+    #[serde(rename = "$createdAt")]
+    pub created_at: String,
+
+    /// Row update date in ISO 8601 format.
+    #[serde(rename = "$updatedAt")]
+    pub updated_at: String,
+
+    /// Row permissions. [Learn more about permissions](https://appwrite.io/docs/permissions).
+    #[serde(rename = "$permissions")]
+    pub permissions: Vec<String>,
+
+    #[serde(flatten)]
+    pub data: HashMap<String, Value>,
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+use super::row::Row;
+
+/// Row List
+///
+/// `T` is [`Row`] unless the rows were read as
+/// [`TypedRow`](super::typed_row::TypedRow)s.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct RowList<T = Row> {
+    /// Total number of rows that matched your query.
+    pub total: u64,
+    /// List of rows.
+    pub rows: Vec<T>,
+}
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{column::Column, column_index::ColumnIndex};
+
+/// Table
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct Table {
+    /// Table ID.
+    #[serde(rename = "$id")]
+    pub id: String,
+
+    /// Table creation date in ISO 8601 format.
+    #[serde(rename = "$createdAt")]
+    pub created_at: String,
+
+    /// Table update date in ISO 8601 format.
+    #[serde(rename = "$updatedAt")]
+    pub updated_at: String,
+
+    /// Table permissions. [Learn more about permissions](https://appwrite.io/docs/permissions).
+    #[serde(rename = "$permissions")]
+    pub permissions: Vec<Value>,
+
+    /// Database ID.
+    #[serde(rename = "databaseId")]
+    pub database_id: String,
+
+    /// Table name.
+    pub name: String,
+
+    /// Table enabled. Can be &#039;enabled&#039; or &#039;disabled&#039;. When disabled, the table is inaccessible to users, but remains accessible to Server SDKs using API keys.
+    pub enabled: bool,
+
+    /// Whether row-level permissions are enabled. [Learn more about permissions](https://appwrite.io/docs/permissions).
+    #[serde(rename = "rowSecurity")]
+    pub row_security: bool,
+
+    /// Table columns.
+    pub columns: Vec<Column>,
+
+    /// Table indexes.
+    pub indexes: Vec<ColumnIndex>,
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+use super::table::Table;
+
+/// Tables List
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct TableList {
+    /// Total number of tables rows that matched your query.
+    pub total: u64,
+    /// List of tables.
+    pub tables: Vec<Table>,
+}
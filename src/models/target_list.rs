@@ -6,7 +6,7 @@ use super::target::Target;
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
 pub struct TargetList {
     /// Total number of targets documents that matched your query.
-    total: usize,
+    pub total: usize,
     /// List of targets.
-    targets: Vec<Target>,
+    pub targets: Vec<Target>,
 }
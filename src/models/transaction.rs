@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+/// Transaction
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct Transaction {
+    /// Transaction ID.
+    #[serde(rename = "$id")]
+    pub id: String,
+
+    /// Transaction creation time in ISO 8601 format.
+    #[serde(rename = "$createdAt")]
+    pub created_at: String,
+
+    /// Transaction update date in ISO 8601 format.
+    #[serde(rename = "$updatedAt")]
+    pub updated_at: String,
+
+    /// Current status of the transaction. Possible values: `pending`, `committing`, `committed`, `rolledBack`, or `failed`
+    pub status: String,
+
+    /// Number of operations in the transaction.
+    pub operations: u64,
+
+    /// Expiration time in ISO 8601 format.
+    #[serde(rename = "expiresAt")]
+    pub expires_at: String,
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+use super::transaction::Transaction;
+
+/// Transaction List
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct TransactionList {
+    /// Total number of transactions that matched your query.
+    pub total: u64,
+    /// List of transactions.
+    pub transactions: Vec<Transaction>,
+}
@@ -0,0 +1,154 @@
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::Error;
+
+use super::document::{deserialize_sequence, Document};
+
+/// Document with its data deserialized into `T`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct TypedDocument<T> {
+    /// Document ID.
+    #[serde(rename = "$id")]
+    pub id: String,
+
+    /// Document automatically incrementing ID.
+    #[serde(
+        rename = "$sequence",
+        default,
+        deserialize_with = "deserialize_sequence",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub sequence: Option<u64>,
+
+    /// Collection ID.
+    #[serde(rename = "$collectionId")]
+    pub collection_id: String,
+
+    /// Database ID.
+    #[serde(rename = "$databaseId")]
+    pub database_id: String,
+
+    /// Document creation date in ISO 8601 format.
+    #[serde(rename = "$createdAt")]
+    pub created_at: String,
+
+    /// Document update date in ISO 8601 format.
+    #[serde(rename = "$updatedAt")]
+    pub updated_at: String,
+
+    /// Document permissions. [Learn more about permissions](https://appwrite.io/docs/permissions).
+    #[serde(rename = "$permissions")]
+    pub permissions: Vec<String>,
+
+    #[serde(flatten)]
+    pub data: T,
+}
+
+impl<T: DeserializeOwned> TypedDocument<T> {
+    /// Deserialize the data of `document`. Errors name the document ID and the
+    /// path of the field that failed.
+    pub fn from_document(document: Document) -> Result<Self, Error> {
+        let data = Value::Object(document.data.into_iter().collect());
+        let data = serde_path_to_error::deserialize(data).map_err(|err| Error::DocumentData {
+            document_id: document.id.clone(),
+            path: err.path().to_string(),
+            message: err.into_inner().to_string(),
+        })?;
+        Ok(Self {
+            id: document.id,
+            sequence: document.sequence,
+            collection_id: document.collection_id,
+            database_id: document.database_id,
+            created_at: document.created_at,
+            updated_at: document.updated_at,
+            permissions: document.permissions,
+            data,
+        })
+    }
+}
+
+impl<T> TypedDocument<T> {
+    pub fn into_inner(self) -> T {
+        self.data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        enumm::HttpMethod,
+        services::server::databases::Databases,
+        transport::{MockResponse, MockTransport},
+    };
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Book {
+        title: String,
+        tags: Vec<String>,
+    }
+
+    fn document(id: &str, data: Value) -> Value {
+        let mut document = json!({
+            "$id": id, "$collectionId": "books", "$databaseId": "db",
+            "$createdAt": "", "$updatedAt": "", "$permissions": [],
+        });
+        document
+            .as_object_mut()
+            .unwrap()
+            .extend(data.as_object().unwrap().clone());
+        document
+    }
+
+    #[tokio::test]
+    async fn test_typed_documents() -> Result<(), Error> {
+        let (mock, client) = MockTransport::client()?;
+        let book = Book {
+            title: "Dune".into(),
+            tags: vec!["sf".into()],
+        };
+
+        mock.on(
+            HttpMethod::POST,
+            "/databases/db/collections/books/documents",
+            MockResponse::json(201, document("dune", json!(book))),
+        );
+        let created = Databases::create_typed_document(
+            &client,
+            "db",
+            "books",
+            &book,
+            HashMap::from([("documentId".to_string(), json!("dune"))]),
+        )
+        .await?;
+        assert_eq!(created.id, "dune");
+        assert_eq!(created.data, book);
+        let request = mock.last_request().expect("request recorded");
+        assert_eq!(request.body.unwrap()["data"]["title"], "Dune");
+
+        mock.on(
+            HttpMethod::GET,
+            "/databases/db/collections/books/documents",
+            MockResponse::json(
+                200,
+                json!({"total": 2, "documents": [
+                    document("dune", json!(book)),
+                    document("broken", json!({"title": "Emma", "tags": ["a", 1]})),
+                ]}),
+            ),
+        );
+        let err = Databases::list_typed_documents::<Book>(&client, "db", "books", HashMap::new())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            &err,
+            Error::DocumentData { document_id, path, .. } if document_id == "broken" && path == "tags[1]"
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::Error;
+
+use super::{document::deserialize_sequence, row::Row};
+
+/// Row with its data deserialized into `T`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct TypedRow<T> {
+    /// Row ID.
+    #[serde(rename = "$id")]
+    pub id: String,
+
+    /// Row automatically incrementing ID.
+    #[serde(
+        rename = "$sequence",
+        default,
+        deserialize_with = "deserialize_sequence",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub sequence: Option<u64>,
+
+    /// Table ID.
+    #[serde(rename = "$tableId")]
+    pub table_id: String,
+
+    /// Database ID.
+    #[serde(rename = "$databaseId")]
+    pub database_id: String,
+
+    /// Row creation date in ISO 8601 format.
+    #[serde(rename = "$createdAt")]
+    pub created_at: String,
+
+    /// Row update date in ISO 8601 format.
+    #[serde(rename = "$updatedAt")]
+    pub updated_at: String,
+
+    /// Row permissions. [Learn more about permissions](https://appwrite.io/docs/permissions).
+    #[serde(rename = "$permissions")]
+    pub permissions: Vec<String>,
+
+    #[serde(flatten)]
+    pub data: T,
+}
+
+impl<T: DeserializeOwned> TypedRow<T> {
+    /// Deserialize the data of `row`. Errors name the row ID and the
+    /// path of the field that failed.
+    pub fn from_row(row: Row) -> Result<Self, Error> {
+        let data = Value::Object(row.data.into_iter().collect());
+        let data = serde_path_to_error::deserialize(data).map_err(|err| Error::RowData {
+            row_id: row.id.clone(),
+            path: err.path().to_string(),
+            message: err.into_inner().to_string(),
+        })?;
+        Ok(Self {
+            id: row.id,
+            sequence: row.sequence,
+            table_id: row.table_id,
+            database_id: row.database_id,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+            permissions: row.permissions,
+            data,
+        })
+    }
+}
+
+impl<T> TypedRow<T> {
+    pub fn into_inner(self) -> T {
+        self.data
+    }
+}
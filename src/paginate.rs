@@ -0,0 +1,391 @@
+//! # Paginate
+//!
+//! Streams over every item of a `list*` endpoint. [`paginate`] calls the
+//! endpoint page by page, moving forward with [`Query::cursor_after`] on the
+//! `$id` of the last item, and keeps the filters and ordering of the caller's
+//! `queries`.
+//! ```no_run
+//! use std::collections::HashMap;
+//!
+//! use futures_util::{pin_mut, StreamExt};
+//! use serde_json::json;
+//! use unofficial_appwrite::{
+//!     client::ClientBuilder,
+//!     paginate::{paginate, PageOptions},
+//!     query::Query,
+//!     services::server::users::Users,
+//! };
+//!
+//! # async fn run() -> Result<(), unofficial_appwrite::error::Error> {
+//! let client = ClientBuilder::from_env()?.build()?;
+//! let mut args = HashMap::new();
+//! args.insert("queries".to_string(), json!([Query::order_asc("name".into())]));
+//!
+//! let users = paginate(args, PageOptions::default(), |args| Users::list(&client, args));
+//! pin_mut!(users);
+//! while let Some(user) = users.next().await {
+//!     println!("{}", user?.name);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use std::{collections::HashMap, future::Future};
+
+use async_fn_stream::try_fn_stream;
+use futures_util::{future::join, Stream, StreamExt};
+use serde_json::{json, Value};
+
+use crate::{
+    error::Error,
+    models::{
+        bucket::Bucket, bucket_list::BucketList, collection::Collection,
+        collection_list::CollectionList, database::Database, database_list::DatabaseList,
+        deployment::Deployment, deployment_list::DeploymentList, document::Document,
+        document_list::DocumentList, execution::Execution, execution_list::ExecutionList,
+        file::File, file_list::FileList, function::Func, function_list::FunctionList,
+        identity::Identity, identity_list::IdentityList, membership::Membership,
+        membership_list::MembershipList, message::Message, message_list::MessageList,
+        provider::Provider, provider_list::ProviderList, row::Row, row_list::RowList,
+        session::Session, session_list::SessionList, subscriber::Subscriber,
+        subscriber_list::SubscriberList, table::Table, table_list::TableList, target::Target,
+        target_list::TargetList, team::Team, team_list::TeamList, topic::Topic,
+        topic_list::TopicList, typed_document::TypedDocument, typed_row::TypedRow, user::User,
+        user_list::UserList, variable::Variable, variable_list::VariableList,
+    },
+    query::Query,
+    query_expr::QueryExpr,
+};
+
+/// Page of a `list*` endpoint whose items have an `$id`.
+pub trait ListPage {
+    type Item;
+
+    fn into_items(self) -> Vec<Self::Item>;
+
+    /// `$id` of an item, the cursor of the next page.
+    fn item_id(item: &Self::Item) -> &str;
+}
+
+macro_rules! list_page {
+    ($($list:ty => $field:ident: $item:ty),* $(,)?) => {$(
+        impl ListPage for $list {
+            type Item = $item;
+
+            fn into_items(self) -> Vec<$item> {
+                self.$field
+            }
+
+            fn item_id(item: &$item) -> &str {
+                &item.id
+            }
+        }
+    )*};
+}
+
+list_page!(
+    BucketList => buckets: Bucket,
+    CollectionList => collections: Collection,
+    DatabaseList => databases: Database,
+    DeploymentList => deployments: Deployment,
+    DocumentList => documents: Document,
+    ExecutionList => executions: Execution,
+    FileList => files: File,
+    FunctionList => functions: Func,
+    IdentityList => identities: Identity,
+    MembershipList => sessions: Membership,
+    MessageList => messages: Message,
+    ProviderList => providers: Provider,
+    RowList => rows: Row,
+    SessionList => sessions: Session,
+    SubscriberList => subscribers: Subscriber,
+    TableList => tables: Table,
+    TargetList => targets: Target,
+    TeamList => teams: Team,
+    TopicList => topics: Topic,
+    UserList => users: User,
+    VariableList => variables: Variable,
+);
+
+impl<T> ListPage for DocumentList<TypedDocument<T>> {
+    type Item = TypedDocument<T>;
+
+    fn into_items(self) -> Vec<TypedDocument<T>> {
+        self.documents
+    }
+
+    fn item_id(item: &TypedDocument<T>) -> &str {
+        &item.id
+    }
+}
+
+impl<T> ListPage for RowList<TypedRow<T>> {
+    type Item = TypedRow<T>;
+
+    fn into_items(self) -> Vec<TypedRow<T>> {
+        self.rows
+    }
+
+    fn item_id(item: &TypedRow<T>) -> &str {
+        &item.id
+    }
+}
+
+/// Settings of [`paginate`].
+#[derive(Debug, Clone)]
+pub struct PageOptions {
+    /// Number of items requested per page.
+    pub page_size: u64,
+    /// Request the next page while the items of the current one are read.
+    pub prefetch: bool,
+}
+
+impl Default for PageOptions {
+    fn default() -> Self {
+        Self {
+            page_size: 100,
+            prefetch: false,
+        }
+    }
+}
+
+/// The caller's queries without their limit, and the `cursorAfter` to
+/// start from. An `offset` or `cursorBefore` can't be combined with paging
+/// forward by cursor and is an [`Error::InvalidQuery`].
+fn base_queries(args: &HashMap<String, Value>) -> Result<(Vec<Value>, Option<String>), Error> {
+    let mut queries = Vec::new();
+    let mut cursor = None;
+    let caller_queries = args.get("queries").and_then(Value::as_array);
+    for query in caller_queries.into_iter().flatten() {
+        match query
+            .as_str()
+            .and_then(|query| query.parse::<QueryExpr>().ok())
+        {
+            Some(QueryExpr::Limit(_)) => {}
+            Some(QueryExpr::CursorAfter(id)) => cursor = Some(id),
+            Some(expr @ (QueryExpr::Offset(_) | QueryExpr::CursorBefore(_))) => {
+                return Err(Error::InvalidQuery(format!(
+                    "{} can't be combined with paginate: {}",
+                    expr.method(),
+                    query
+                )));
+            }
+            _ => queries.push(query.clone()),
+        }
+    }
+    Ok((queries, cursor))
+}
+
+/// `args` for the page after `cursor`: the base queries, then the page
+/// limit and cursor.
+fn page_args(
+    args: &HashMap<String, Value>,
+    base: &[Value],
+    page_size: u64,
+    cursor: Option<&str>,
+) -> HashMap<String, Value> {
+    let mut queries = base.to_vec();
+    queries.push(json!(Query::limit(page_size.into())));
+    if let Some(cursor) = cursor {
+        queries.push(json!(Query::cursor_after(cursor.into())));
+    }
+
+    let mut args = args.clone();
+    args.insert("queries".to_string(), Value::Array(queries));
+    args
+}
+
+/// Stream every item of a `list*` endpoint. `fetch` is called with `args`
+/// and the queries of each page. A `limit` in `args` is replaced by the page
+/// limit, and a `cursorAfter` is the cursor of the first page. The stream
+/// fails with [`Error::InvalidQuery`] on an `offset` or `cursorBefore`.
+pub fn paginate<'a, P, F, Fut>(
+    args: HashMap<String, Value>,
+    options: PageOptions,
+    fetch: F,
+) -> impl Stream<Item = Result<P::Item, Error>> + 'a
+where
+    P: ListPage + 'a,
+    P::Item: 'a,
+    F: Fn(HashMap<String, Value>) -> Fut + 'a,
+    Fut: Future<Output = Result<P, Error>> + 'a,
+{
+    try_fn_stream(|emitter| async move {
+        let page_size = options.page_size.max(1);
+        let (base, cursor) = base_queries(&args)?;
+        let first = page_args(&args, &base, page_size, cursor.as_deref());
+        let mut items = fetch(first).await?.into_items();
+        loop {
+            let next = match items.last() {
+                Some(last) if items.len() as u64 >= page_size => {
+                    Some(page_args(&args, &base, page_size, Some(P::item_id(last))))
+                }
+                _ => None,
+            };
+            let next_page = match next {
+                None => {
+                    for item in items {
+                        emitter.emit(item).await;
+                    }
+                    return Ok(());
+                }
+                Some(next) if options.prefetch => {
+                    let emit_all = async {
+                        for item in items {
+                            emitter.emit(item).await;
+                        }
+                    };
+                    join(fetch(next), emit_all).await.0
+                }
+                Some(next) => {
+                    for item in items {
+                        emitter.emit(item).await;
+                    }
+                    fetch(next).await
+                }
+            };
+            items = next_page?.into_items();
+        }
+    })
+}
+
+/// Collect every item of `stream`, failing with [`Error::TooManyItems`]
+/// instead of reading more than `max_items`.
+pub async fn collect_all<T>(
+    stream: impl Stream<Item = Result<T, Error>>,
+    max_items: usize,
+) -> Result<Vec<T>, Error> {
+    let mut items = Vec::new();
+    futures_util::pin_mut!(stream);
+    while let Some(item) = stream.next().await {
+        let item = item?;
+        if items.len() == max_items {
+            return Err(Error::TooManyItems(max_items));
+        }
+        items.push(item);
+    }
+    Ok(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        enumm::HttpMethod,
+        services::server::databases::Databases,
+        transport::{MockResponse, MockTransport},
+    };
+
+    fn page(ids: &[&str]) -> MockResponse {
+        let documents: Vec<_> = ids
+            .iter()
+            .map(|id| {
+                json!({"$id": id, "$collectionId": "books", "$databaseId": "db",
+                       "$createdAt": "", "$updatedAt": "", "$permissions": []})
+            })
+            .collect();
+        MockResponse::json(200, json!({"total": 5, "documents": documents}))
+    }
+
+    #[tokio::test]
+    async fn test_paginate_documents() -> Result<(), Error> {
+        for prefetch in [false, true] {
+            let (mock, client) = MockTransport::client()?;
+            let path = "/databases/db/collections/books/documents";
+            mock.on(HttpMethod::GET, path, page(&["a", "b"]))
+                .on(HttpMethod::GET, path, page(&["c", "d"]))
+                .on(HttpMethod::GET, path, page(&["e"]));
+
+            let mut args = HashMap::new();
+            args.insert(
+                "queries".to_string(),
+                json!([
+                    Query::equal("genre".into(), "sf".into()),
+                    Query::limit(1.into())
+                ]),
+            );
+            let documents = paginate(
+                args,
+                PageOptions {
+                    page_size: 2,
+                    prefetch,
+                },
+                |args| Databases::list_documents(&client, "db", "books", args),
+            );
+            let ids: Vec<_> = collect_all(documents, 10)
+                .await?
+                .into_iter()
+                .map(|document| document.id)
+                .collect();
+            assert_eq!(ids, ["a", "b", "c", "d", "e"]);
+
+            let requests = mock.requests();
+            assert_eq!(requests.len(), 3);
+            let queries: Vec<_> = requests[1]
+                .query
+                .iter()
+                .filter(|(key, _)| key.starts_with("queries"))
+                .map(|(_, query)| query.parse::<QueryExpr>().unwrap())
+                .collect();
+            assert_eq!(
+                queries,
+                [
+                    QueryExpr::filter(
+                        crate::query_expr::FilterOp::Equal,
+                        "genre",
+                        vec![json!("sf")]
+                    ),
+                    QueryExpr::Limit(2),
+                    QueryExpr::CursorAfter("b".to_string()),
+                ]
+            );
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_collect_all_bound() {
+        let items = futures_util::stream::iter((0..5).map(Ok::<_, Error>));
+        let err = collect_all(items, 4).await.unwrap_err();
+        assert!(matches!(err, Error::TooManyItems(4)));
+
+        let items = futures_util::stream::iter((0..4).map(Ok::<_, Error>));
+        assert_eq!(collect_all(items, 4).await.unwrap(), [0, 1, 2, 3]);
+
+        let failed = Err(Error::InvalidQuery("page".to_string()));
+        let items = futures_util::stream::iter([Ok(0), Ok(1), failed]);
+        let err = collect_all(items, 2).await.unwrap_err();
+        assert!(matches!(err, Error::InvalidQuery(_)));
+    }
+
+    #[tokio::test]
+    async fn test_paginate_resumes_from_cursor() -> Result<(), Error> {
+        let (mock, client) = MockTransport::client()?;
+        let path = "/databases/db/collections/books/documents";
+        mock.on(HttpMethod::GET, path, page(&["c"]));
+
+        let list = |queries: Value| {
+            let args = HashMap::from([("queries".to_string(), queries)]);
+            let documents = paginate(args, PageOptions::default(), |args| {
+                Databases::list_documents(&client, "db", "books", args)
+            });
+            collect_all(documents, 10)
+        };
+
+        let documents = list(json!([Query::cursor_after("b".into())])).await?;
+        assert_eq!(documents[0].id, "c");
+        let cursors: Vec<_> = mock.requests()[0]
+            .query
+            .iter()
+            .filter(|(_, query)| query.contains("cursor"))
+            .map(|(_, query)| query.parse::<QueryExpr>().unwrap())
+            .collect();
+        assert_eq!(cursors, [QueryExpr::CursorAfter("b".to_string())]);
+
+        for query in [Query::offset(5.into()), Query::cursor_before("b".into())] {
+            let err = list(json!([query])).await.unwrap_err();
+            assert!(matches!(err, Error::InvalidQuery(_)));
+        }
+        assert_eq!(mock.requests().len(), 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,275 @@
+//! # Certificate pinning
+//!
+//! Pinned SHA-256 fingerprints of the server leaf certificate, checked on
+//! every new REST and realtime connection on top of the usual certificate
+//! validation. A connection whose certificate is not pinned fails with
+//! [`Error::Tls`] holding a [`PinMismatch`].
+//! ```no_run
+//! use unofficial_appwrite::client::ClientBuilder;
+//!
+//! # fn run() -> Result<(), unofficial_appwrite::error::Error> {
+//! let client = ClientBuilder::default()
+//!     .set_endpoint("https://appwrite.example.com/v1")?
+//!     .add_pinned_certificate_sha256(
+//!         "7A:69:82:6D:B0:AB:F9:C2:7D:7F:BD:99:44:A8:1A:B4:02:16:6C:1F:FC:0E:07:A9:33:EF:15:47:B6:4B:31:16",
+//!     )?
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+use std::{
+    fmt,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use futures_util::future::BoxFuture;
+use hyper_util::client::legacy::connect::Connection;
+
+use crate::error::Error;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// SHA-256 fingerprint of a DER encoded certificate.
+pub type Fingerprint = [u8; 32];
+
+/// Server certificate which matched none of the pinned fingerprints.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PinMismatch {
+    /// Fingerprint of the server leaf certificate, `None` when the
+    /// connection had no certificate, e.g. plain `http://`.
+    pub fingerprint: Option<Fingerprint>,
+}
+
+impl fmt::Display for PinMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.fingerprint {
+            Some(fingerprint) => write!(
+                f,
+                "server certificate {} is not pinned",
+                to_hex(fingerprint)
+            ),
+            None => write!(f, "no server certificate to check against the pins"),
+        }
+    }
+}
+
+impl std::error::Error for PinMismatch {}
+
+/// SHA-256 fingerprint of a DER encoded certificate.
+pub fn fingerprint(der: &[u8]) -> Fingerprint {
+    let digest = ring::digest::digest(&ring::digest::SHA256, der);
+    let mut fingerprint = [0; 32];
+    fingerprint.copy_from_slice(digest.as_ref());
+    fingerprint
+}
+
+/// Parse a hex fingerprint, with or without `:` separators.
+pub(crate) fn parse_fingerprint(hex: &str) -> Option<Fingerprint> {
+    let digits: Vec<u8> = hex.bytes().filter(|byte| *byte != b':').collect();
+    if digits.len() != 64 {
+        return None;
+    }
+    let mut fingerprint = [0; 32];
+    for (byte, pair) in fingerprint.iter_mut().zip(digits.chunks(2)) {
+        *byte = u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok()?;
+    }
+    Some(fingerprint)
+}
+
+fn to_hex(fingerprint: &Fingerprint) -> String {
+    fingerprint
+        .iter()
+        .map(|byte| format!("{:02X}", byte))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// Check the DER leaf certificate of a connection against `pins`.
+pub(crate) fn check(pins: &[Fingerprint], der: Option<&[u8]>) -> Result<(), PinMismatch> {
+    let fingerprint = der.map(fingerprint);
+    match fingerprint {
+        Some(fingerprint) if pins.contains(&fingerprint) => Ok(()),
+        fingerprint => Err(PinMismatch { fingerprint }),
+    }
+}
+
+/// [`Error::Tls`] when a pin check failed somewhere in `err`, else
+/// [`Error::Network`].
+pub(crate) fn network_error(err: reqwest::Error) -> Error {
+    let mut source = std::error::Error::source(&err);
+    while let Some(inner) = source {
+        if let Some(mismatch) = inner.downcast_ref::<PinMismatch>() {
+            return Error::Tls(Box::new(mismatch.clone()));
+        }
+        source = inner.source();
+    }
+    Error::Network(err)
+}
+
+/// Connector layer of the REST transport checking the pins once a
+/// connection is established, before any request is written to it.
+#[derive(Debug, Clone)]
+pub(crate) struct PinLayer {
+    pins: Arc<[Fingerprint]>,
+}
+
+impl PinLayer {
+    pub(crate) fn new(pins: &[Fingerprint]) -> Self {
+        Self { pins: pins.into() }
+    }
+}
+
+impl<S> tower_layer::Layer<S> for PinLayer {
+    type Service = PinService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        PinService {
+            inner,
+            pins: self.pins.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct PinService<S> {
+    inner: S,
+    pins: Arc<[Fingerprint]>,
+}
+
+impl<S, R> tower_service::Service<R> for PinService<S>
+where
+    S: tower_service::Service<R>,
+    S::Response: Connection + Send + 'static,
+    S::Error: Into<BoxError>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = BoxError;
+    type Future = BoxFuture<'static, Result<S::Response, BoxError>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), BoxError>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, request: R) -> Self::Future {
+        let connecting = self.inner.call(request);
+        let pins = self.pins.clone();
+        Box::pin(async move {
+            let conn = connecting.await.map_err(Into::into)?;
+            let mut extensions = http::Extensions::new();
+            conn.connected().get_extras(&mut extensions);
+            let der = extensions
+                .get::<reqwest::tls::TlsInfo>()
+                .and_then(|info| info.peer_certificate());
+            check(&pins, der)?;
+            Ok(conn)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::StreamExt;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+    use crate::{
+        client::{ClientBuilder, TlsConfig},
+        services::server::users::Users,
+    };
+
+    const CERT: &[u8] = include_bytes!("../tests/data/localhost.pem");
+    const KEY: &[u8] = include_bytes!("../tests/data/localhost.key");
+    const PIN: &str = "7a69826db0abf9c27d7fbd9944a81ab402166c1ffc0e07a933ef1547b64b3116";
+    const OTHER_PIN: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+    /// TLS server on 127.0.0.1 answering every connection with `serve`.
+    async fn tls_server<F, Fut>(serve: F) -> u16
+    where
+        F: Fn(tokio_native_tls::TlsStream<tokio::net::TcpStream>) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        let identity = native_tls::Identity::from_pkcs8(CERT, KEY).unwrap();
+        let acceptor =
+            tokio_native_tls::TlsAcceptor::from(native_tls::TlsAcceptor::new(identity).unwrap());
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                if let Ok(stream) = acceptor.accept(socket).await {
+                    tokio::spawn(serve(stream));
+                }
+            }
+        });
+        port
+    }
+
+    #[tokio::test]
+    async fn test_pinned_rest_connection() -> Result<(), Error> {
+        let port = tls_server(|mut stream| async move {
+            let mut buf = [0; 4096];
+            let _ = stream.read(&mut buf).await;
+            let _ = stream
+                .write_all(
+                    b"HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: 2\r\n\r\n{}",
+                )
+                .await;
+        })
+        .await;
+        let endpoint = format!("https://127.0.0.1:{}/v1", port);
+
+        let client = ClientBuilder::default()
+            .set_endpoint(&endpoint)?
+            .add_ca_certificate_pem(CERT)?
+            .add_pinned_certificate_pem(CERT)?
+            .build()?;
+        Users::get_prefs(&client, "user").await?;
+
+        let client = ClientBuilder::default()
+            .set_endpoint(&endpoint)?
+            .add_ca_certificate_pem(CERT)?
+            .add_pinned_certificate_sha256(OTHER_PIN)?
+            .build()?;
+        let err = Users::get_prefs(&client, "user").await.unwrap_err();
+        let Error::Tls(source) = err else {
+            panic!("expected a tls error, got {:?}", err);
+        };
+        let mismatch = source.downcast_ref::<PinMismatch>().unwrap();
+        assert_eq!(mismatch.fingerprint, parse_fingerprint(PIN));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pinned_websocket_connection() -> Result<(), Error> {
+        let port = tls_server(|stream| async move {
+            if let Ok(mut ws) = tokio_tungstenite::accept_async(stream).await {
+                let _ = ws.next().await;
+            }
+        })
+        .await;
+        let url = format!("wss://127.0.0.1:{}/v1/realtime", port);
+        let tls = |pin: &str| TlsConfig {
+            ca_certificates: vec![CERT.to_vec()],
+            pinned_certificates: vec![parse_fingerprint(pin).unwrap()],
+            ..Default::default()
+        };
+
+        tls(PIN).connect_websocket(&url).await?;
+        let err = tls(OTHER_PIN).connect_websocket(&url).await.unwrap_err();
+        assert!(matches!(&err, Error::Tls(source) if source.is::<PinMismatch>()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_fingerprint() {
+        let hex = "7a69826db0abf9c27d7fbd9944a81ab402166c1ffc0e07a933ef1547b64b3116";
+        let fingerprint = parse_fingerprint(hex).unwrap();
+        assert_eq!(parse_fingerprint(&to_hex(&fingerprint)), Some(fingerprint));
+        assert!(parse_fingerprint("7a:69").is_none());
+        assert!(parse_fingerprint(&"zz".repeat(32)).is_none());
+    }
+}
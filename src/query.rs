@@ -1,7 +1,16 @@
-use crate::query_value::{Query as Q, QueryValue};
+use crate::{
+    error::Error,
+    query_expr::{attribute_name, query_values, FilterOp, QueryExpr},
+    query_value::QueryValue,
+};
+use chrono::{DateTime, TimeZone};
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 
 /// Helper class to generate query strings.
+///
+/// Every helper renders a [`QueryExpr`]; use it directly to inspect or
+/// validate queries before sending them.
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Query;
 
@@ -25,7 +34,7 @@ impl Query {
     ///     );
     /// ```
     pub fn equal(attribute: QueryValue, values: QueryValue) -> String {
-        Q::new("equal", attribute, values).to_string()
+        filter(FilterOp::Equal, attribute, values)
     }
 
     /// Filter resources where [attribute] is not equal to [value].
@@ -36,7 +45,7 @@ impl Query {
     ///     );
     /// ```
     pub fn not_equal(attribute: QueryValue, values: QueryValue) -> String {
-        Q::new("notEqual", attribute, values).to_string()
+        filter(FilterOp::NotEqual, attribute, values)
     }
 
     /// Filter resources where [attribute] is less than [value].
@@ -47,7 +56,7 @@ impl Query {
     ///     );
     /// ```
     pub fn less_than(attribute: QueryValue, value: QueryValue) -> String {
-        Q::new("lessThan", attribute, value.convert_to_array_if_not_array()).to_string()
+        filter(FilterOp::LessThan, attribute, value)
     }
 
     /// Filter resources where [attribute] is less than or equal to [value].
@@ -58,12 +67,7 @@ impl Query {
     ///     );
     /// ```
     pub fn less_than_equal(attribute: QueryValue, value: QueryValue) -> String {
-        Q::new(
-            "lessThanEqual",
-            attribute,
-            value.convert_to_array_if_not_array(),
-        )
-        .to_string()
+        filter(FilterOp::LessThanEqual, attribute, value)
     }
 
     /// Filter resources where [attribute] is greater than [value].
@@ -74,12 +78,7 @@ impl Query {
     ///     );
     /// ```
     pub fn greater_than(attribute: QueryValue, value: QueryValue) -> String {
-        Q::new(
-            "greaterThan",
-            attribute,
-            value.convert_to_array_if_not_array(),
-        )
-        .to_string()
+        filter(FilterOp::GreaterThan, attribute, value)
     }
 
     /// Filter resources where [attribute] is greater than or equal to [value].
@@ -90,12 +89,7 @@ impl Query {
     ///     );
     /// ```
     pub fn greater_than_equal(attribute: QueryValue, value: QueryValue) -> String {
-        Q::new(
-            "greaterThanEqual",
-            attribute,
-            value.convert_to_array_if_not_array(),
-        )
-        .to_string()
+        filter(FilterOp::GreaterThanEqual, attribute, value)
     }
 
     /// Filter resources where by searching [attribute] for [value].
@@ -106,7 +100,7 @@ impl Query {
     ///     );
     /// ```
     pub fn search(attribute: QueryValue, value: QueryValue) -> String {
-        Q::new("search", attribute, value.convert_to_array_if_not_array()).to_string()
+        filter(FilterOp::Search, attribute, value)
     }
 
     /// Filter resources where [attribute] is null.
@@ -117,7 +111,7 @@ impl Query {
     ///     );
     /// ```
     pub fn is_null(attribute: QueryValue) -> String {
-        Q::new("isNull", attribute, None::<&str>.into()).to_string()
+        filter(FilterOp::IsNull, attribute, QueryValue::Null)
     }
 
     /// Filter resources where [attribute] is not null.
@@ -128,7 +122,7 @@ impl Query {
     ///     );
     /// ```
     pub fn is_not_null(attribute: QueryValue) -> String {
-        Q::new("isNotNull", attribute, None::<&str>.into()).to_string()
+        filter(FilterOp::IsNotNull, attribute, QueryValue::Null)
     }
 
     /// Filter resources where [attribute] is between [start] and [end] (inclusive).
@@ -139,7 +133,7 @@ impl Query {
     ///     );
     /// ```
     pub fn between(attribute: QueryValue, start: QueryValue, end: QueryValue) -> String {
-        Q::new("between", attribute, vec![start, end].into()).to_string()
+        filter(FilterOp::Between, attribute, vec![start, end].into())
     }
 
     /// Filter resources where [attribute] starts with [value].
@@ -150,12 +144,7 @@ impl Query {
     ///     );
     /// ```
     pub fn starts_with(attribute: QueryValue, value: QueryValue) -> String {
-        Q::new(
-            "startsWith",
-            attribute,
-            value.convert_to_array_if_not_array(),
-        )
-        .to_string()
+        filter(FilterOp::StartsWith, attribute, value)
     }
 
     /// Filter resources where [attribute] ends with [value].
@@ -166,7 +155,7 @@ impl Query {
     ///     );
     /// ```
     pub fn ends_with(attribute: QueryValue, value: QueryValue) -> String {
-        Q::new("endsWith", attribute, value.convert_to_array_if_not_array()).to_string()
+        filter(FilterOp::EndsWith, attribute, value)
     }
 
     /// Filter resources where [attribute] contains [value]
@@ -185,7 +174,323 @@ impl Query {
     ///     );
     /// ```
     pub fn contains(attribute: QueryValue, value: QueryValue) -> String {
-        Q::new("contains", attribute, value).to_string()
+        filter(FilterOp::Contains, attribute, value)
+    }
+
+    /// Filter resources where [attribute] does not contain [value]
+    /// [value] can be a single value or a list.
+    /// ```
+    /// assert_eq!(
+    ///         unofficial_appwrite::query::Query::not_contains("ingredients".into(), vec!["apple", "banana"].into()),
+    ///         r#"{"method":"notContains","attribute":"ingredients","values":["apple","banana"]}"#
+    ///     );
+    /// ```
+    pub fn not_contains(attribute: QueryValue, value: QueryValue) -> String {
+        filter(FilterOp::NotContains, attribute, value)
+    }
+
+    /// Filter resources where by searching [attribute] does not match [value].
+    /// ```
+    /// assert_eq!(
+    ///         unofficial_appwrite::query::Query::not_search("text".into(), "key words".into()),
+    ///         r#"{"method":"notSearch","attribute":"text","values":["key words"]}"#
+    ///     );
+    /// ```
+    pub fn not_search(attribute: QueryValue, value: QueryValue) -> String {
+        filter(FilterOp::NotSearch, attribute, value)
+    }
+
+    /// Filter resources where [attribute] is not between [start] and [end] (exclusive).
+    /// ```
+    /// assert_eq!(
+    ///         unofficial_appwrite::query::Query::not_between("price".into(), 5.into(), 10.into()),
+    ///         r#"{"method":"notBetween","attribute":"price","values":[5,10]}"#
+    ///     );
+    /// ```
+    pub fn not_between(attribute: QueryValue, start: QueryValue, end: QueryValue) -> String {
+        filter(FilterOp::NotBetween, attribute, vec![start, end].into())
+    }
+
+    /// Filter resources where [attribute] does not start with [value].
+    /// ```
+    /// assert_eq!(
+    ///         unofficial_appwrite::query::Query::not_starts_with("name".into(), "Once upon a time".into()),
+    ///         r#"{"method":"notStartsWith","attribute":"name","values":["Once upon a time"]}"#
+    ///     );
+    /// ```
+    pub fn not_starts_with(attribute: QueryValue, value: QueryValue) -> String {
+        filter(FilterOp::NotStartsWith, attribute, value)
+    }
+
+    /// Filter resources where [attribute] does not end with [value].
+    /// ```
+    /// assert_eq!(
+    ///         unofficial_appwrite::query::Query::not_ends_with("name".into(), "happily ever after.".into()),
+    ///         r#"{"method":"notEndsWith","attribute":"name","values":["happily ever after."]}"#
+    ///     );
+    /// ```
+    pub fn not_ends_with(attribute: QueryValue, value: QueryValue) -> String {
+        filter(FilterOp::NotEndsWith, attribute, value)
+    }
+
+    /// Filter resources created before [value].
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// assert_eq!(
+    ///         unofficial_appwrite::query::Query::created_before(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()),
+    ///         r#"{"method":"lessThan","attribute":"$createdAt","values":["2024-01-01T00:00:00.000+00:00"]}"#
+    ///     );
+    /// ```
+    pub fn created_before<Tz: TimeZone>(value: DateTime<Tz>) -> String {
+        date_filter(FilterOp::LessThan, "$createdAt", &[value])
+    }
+
+    /// Filter resources created after [value].
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// assert_eq!(
+    ///         unofficial_appwrite::query::Query::created_after(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()),
+    ///         r#"{"method":"greaterThan","attribute":"$createdAt","values":["2024-01-01T00:00:00.000+00:00"]}"#
+    ///     );
+    /// ```
+    pub fn created_after<Tz: TimeZone>(value: DateTime<Tz>) -> String {
+        date_filter(FilterOp::GreaterThan, "$createdAt", &[value])
+    }
+
+    /// Filter resources created between [start] and [end] (inclusive).
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// assert_eq!(
+    ///         unofficial_appwrite::query::Query::created_between(
+    ///             Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+    ///             Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap(),
+    ///         ),
+    ///         r#"{"method":"between","attribute":"$createdAt","values":["2024-01-01T00:00:00.000+00:00","2024-02-01T00:00:00.000+00:00"]}"#
+    ///     );
+    /// ```
+    pub fn created_between<Tz: TimeZone>(start: DateTime<Tz>, end: DateTime<Tz>) -> String {
+        date_filter(FilterOp::Between, "$createdAt", &[start, end])
+    }
+
+    /// Filter resources updated before [value].
+    /// ```
+    /// use chrono::{FixedOffset, TimeZone};
+    /// let offset = FixedOffset::east_opt(3600).unwrap();
+    /// assert_eq!(
+    ///         unofficial_appwrite::query::Query::updated_before(offset.with_ymd_and_hms(2024, 1, 1, 1, 0, 0).unwrap()),
+    ///         r#"{"method":"lessThan","attribute":"$updatedAt","values":["2024-01-01T00:00:00.000+00:00"]}"#
+    ///     );
+    /// ```
+    pub fn updated_before<Tz: TimeZone>(value: DateTime<Tz>) -> String {
+        date_filter(FilterOp::LessThan, "$updatedAt", &[value])
+    }
+
+    /// Filter resources updated after [value].
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// assert_eq!(
+    ///         unofficial_appwrite::query::Query::updated_after(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()),
+    ///         r#"{"method":"greaterThan","attribute":"$updatedAt","values":["2024-01-01T00:00:00.000+00:00"]}"#
+    ///     );
+    /// ```
+    pub fn updated_after<Tz: TimeZone>(value: DateTime<Tz>) -> String {
+        date_filter(FilterOp::GreaterThan, "$updatedAt", &[value])
+    }
+
+    /// Filter resources updated between [start] and [end] (inclusive).
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// assert_eq!(
+    ///         unofficial_appwrite::query::Query::updated_between(
+    ///             Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+    ///             Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap(),
+    ///         ),
+    ///         r#"{"method":"between","attribute":"$updatedAt","values":["2024-01-01T00:00:00.000+00:00","2024-02-01T00:00:00.000+00:00"]}"#
+    ///     );
+    /// ```
+    pub fn updated_between<Tz: TimeZone>(start: DateTime<Tz>, end: DateTime<Tz>) -> String {
+        date_filter(FilterOp::Between, "$updatedAt", &[start, end])
+    }
+
+    /// Filter resources where [attribute] is within [distance] of [values].
+    ///
+    /// [values] is a point, line or polygon. [distance] is in meters when
+    /// [meters] is true, in the units of the coordinate system otherwise.
+    /// ```
+    /// assert_eq!(
+    ///         unofficial_appwrite::query::Query::distance_less_than("location".into(), vec![40.7, -74.0].into(), 1000.0, true),
+    ///         r#"{"method":"distanceLessThan","attribute":"location","values":[[[40.7,-74.0],1000.0,true]]}"#
+    ///     );
+    /// ```
+    pub fn distance_less_than(
+        attribute: QueryValue,
+        values: QueryValue,
+        distance: f64,
+        meters: bool,
+    ) -> String {
+        distance_filter(
+            FilterOp::DistanceLessThan,
+            attribute,
+            values,
+            distance,
+            meters,
+        )
+    }
+
+    /// Filter resources where [attribute] is further than [distance] from [values].
+    /// ```
+    /// assert_eq!(
+    ///         unofficial_appwrite::query::Query::distance_greater_than("location".into(), vec![40.7, -74.0].into(), 1000.0, true),
+    ///         r#"{"method":"distanceGreaterThan","attribute":"location","values":[[[40.7,-74.0],1000.0,true]]}"#
+    ///     );
+    /// ```
+    pub fn distance_greater_than(
+        attribute: QueryValue,
+        values: QueryValue,
+        distance: f64,
+        meters: bool,
+    ) -> String {
+        distance_filter(
+            FilterOp::DistanceGreaterThan,
+            attribute,
+            values,
+            distance,
+            meters,
+        )
+    }
+
+    /// Filter resources where [attribute] is exactly [distance] from [values].
+    /// ```
+    /// assert_eq!(
+    ///         unofficial_appwrite::query::Query::distance_equal("location".into(), vec![40.7, -74.0].into(), 0.0, false),
+    ///         r#"{"method":"distanceEqual","attribute":"location","values":[[[40.7,-74.0],0.0,false]]}"#
+    ///     );
+    /// ```
+    pub fn distance_equal(
+        attribute: QueryValue,
+        values: QueryValue,
+        distance: f64,
+        meters: bool,
+    ) -> String {
+        distance_filter(FilterOp::DistanceEqual, attribute, values, distance, meters)
+    }
+
+    /// Filter resources where [attribute] is not exactly [distance] from [values].
+    /// ```
+    /// assert_eq!(
+    ///         unofficial_appwrite::query::Query::distance_not_equal("location".into(), vec![40.7, -74.0].into(), 0.0, false),
+    ///         r#"{"method":"distanceNotEqual","attribute":"location","values":[[[40.7,-74.0],0.0,false]]}"#
+    ///     );
+    /// ```
+    pub fn distance_not_equal(
+        attribute: QueryValue,
+        values: QueryValue,
+        distance: f64,
+        meters: bool,
+    ) -> String {
+        distance_filter(
+            FilterOp::DistanceNotEqual,
+            attribute,
+            values,
+            distance,
+            meters,
+        )
+    }
+
+    /// Filter resources where the geometry in [attribute] intersects [values].
+    /// ```
+    /// assert_eq!(
+    ///         unofficial_appwrite::query::Query::intersects("area".into(), vec![40.7, -74.0].into()),
+    ///         r#"{"method":"intersects","attribute":"area","values":[[40.7,-74.0]]}"#
+    ///     );
+    /// ```
+    pub fn intersects(attribute: QueryValue, values: QueryValue) -> String {
+        geometry_filter(FilterOp::Intersects, attribute, values)
+    }
+
+    /// Filter resources where the geometry in [attribute] does not intersect [values].
+    /// ```
+    /// assert_eq!(
+    ///         unofficial_appwrite::query::Query::not_intersects("area".into(), vec![40.7, -74.0].into()),
+    ///         r#"{"method":"notIntersects","attribute":"area","values":[[40.7,-74.0]]}"#
+    ///     );
+    /// ```
+    pub fn not_intersects(attribute: QueryValue, values: QueryValue) -> String {
+        geometry_filter(FilterOp::NotIntersects, attribute, values)
+    }
+
+    /// Filter resources where the geometry in [attribute] crosses [values].
+    /// ```
+    /// use unofficial_appwrite::query_value::QueryValue;
+    /// let line: QueryValue = vec![QueryValue::from(vec![1.0, 2.0]), vec![3.0, 4.0].into()].into();
+    /// assert_eq!(
+    ///         unofficial_appwrite::query::Query::crosses("route".into(), line),
+    ///         r#"{"method":"crosses","attribute":"route","values":[[[1.0,2.0],[3.0,4.0]]]}"#
+    ///     );
+    /// ```
+    pub fn crosses(attribute: QueryValue, values: QueryValue) -> String {
+        geometry_filter(FilterOp::Crosses, attribute, values)
+    }
+
+    /// Filter resources where the geometry in [attribute] does not cross [values].
+    /// ```
+    /// use unofficial_appwrite::query_value::QueryValue;
+    /// let line: QueryValue = vec![QueryValue::from(vec![1.0, 2.0]), vec![3.0, 4.0].into()].into();
+    /// assert_eq!(
+    ///         unofficial_appwrite::query::Query::not_crosses("route".into(), line),
+    ///         r#"{"method":"notCrosses","attribute":"route","values":[[[1.0,2.0],[3.0,4.0]]]}"#
+    ///     );
+    /// ```
+    pub fn not_crosses(attribute: QueryValue, values: QueryValue) -> String {
+        geometry_filter(FilterOp::NotCrosses, attribute, values)
+    }
+
+    /// Filter resources where the geometry in [attribute] overlaps [values].
+    /// ```
+    /// use unofficial_appwrite::query_value::QueryValue;
+    /// let line: QueryValue = vec![QueryValue::from(vec![1.0, 2.0]), vec![3.0, 4.0].into()].into();
+    /// assert_eq!(
+    ///         unofficial_appwrite::query::Query::overlaps("area".into(), line),
+    ///         r#"{"method":"overlaps","attribute":"area","values":[[[1.0,2.0],[3.0,4.0]]]}"#
+    ///     );
+    /// ```
+    pub fn overlaps(attribute: QueryValue, values: QueryValue) -> String {
+        geometry_filter(FilterOp::Overlaps, attribute, values)
+    }
+
+    /// Filter resources where the geometry in [attribute] does not overlap [values].
+    /// ```
+    /// use unofficial_appwrite::query_value::QueryValue;
+    /// let line: QueryValue = vec![QueryValue::from(vec![1.0, 2.0]), vec![3.0, 4.0].into()].into();
+    /// assert_eq!(
+    ///         unofficial_appwrite::query::Query::not_overlaps("area".into(), line),
+    ///         r#"{"method":"notOverlaps","attribute":"area","values":[[[1.0,2.0],[3.0,4.0]]]}"#
+    ///     );
+    /// ```
+    pub fn not_overlaps(attribute: QueryValue, values: QueryValue) -> String {
+        geometry_filter(FilterOp::NotOverlaps, attribute, values)
+    }
+
+    /// Filter resources where the geometry in [attribute] touches [values].
+    /// ```
+    /// assert_eq!(
+    ///         unofficial_appwrite::query::Query::touches("area".into(), vec![40.7, -74.0].into()),
+    ///         r#"{"method":"touches","attribute":"area","values":[[40.7,-74.0]]}"#
+    ///     );
+    /// ```
+    pub fn touches(attribute: QueryValue, values: QueryValue) -> String {
+        geometry_filter(FilterOp::Touches, attribute, values)
+    }
+
+    /// Filter resources where the geometry in [attribute] does not touch [values].
+    /// ```
+    /// assert_eq!(
+    ///         unofficial_appwrite::query::Query::not_touches("area".into(), vec![40.7, -74.0].into()),
+    ///         r#"{"method":"notTouches","attribute":"area","values":[[40.7,-74.0]]}"#
+    ///     );
+    /// ```
+    pub fn not_touches(attribute: QueryValue, values: QueryValue) -> String {
+        geometry_filter(FilterOp::NotTouches, attribute, values)
     }
 
     ///```
@@ -200,13 +505,16 @@ impl Query {
     ///         r#"{"method":"or","values":[{"attribute":"size","method":"lessThan","values":[5]},{"attribute":"size","method":"greaterThan","values":[10]}]}"#
     ///     );
     /// ```
+    ///
+    /// Queries which do not parse are sent as given, see [`Query::try_or`].
     pub fn or(queries: Vec<String>) -> String {
-        let values = queries
-            .into_iter()
-            .map(|q| QueryValue::JsonObject(serde_json::from_str(&q).expect("Invalid JSON string")))
-            .collect();
+        Self::try_or(queries.clone()).unwrap_or_else(|_| raw_logical("or", &queries))
+    }
 
-        Q::new("or", None::<&str>.into(), QueryValue::Array(values)).to_string()
+    /// Same as [`Query::or`] but returns [`Error::InvalidQuery`] when one of
+    /// `queries` is not a valid query.
+    pub fn try_or(queries: Vec<String>) -> Result<String, Error> {
+        Ok(QueryExpr::Or(parse_all(&queries)?).to_string())
     }
 
     ///```
@@ -218,17 +526,21 @@ impl Query {
     ///         r#"{"method":"and","values":[{"attribute":"size","method":"lessThan","values":[10]},{"attribute":"size","method":"greaterThan","values":[5]}]}"#
     ///     );
     /// ```
+    ///
+    /// Queries which do not parse are sent as given, see [`Query::try_and`].
     pub fn and(queries: Vec<String>) -> String {
-        let values = queries
-            .into_iter()
-            .map(|q| QueryValue::JsonObject(serde_json::from_str(&q).expect("Invalid JSON string")))
-            .collect();
-        Q::new("and", None::<&str>.into(), QueryValue::Array(values)).to_string()
+        Self::try_and(queries.clone()).unwrap_or_else(|_| raw_logical("and", &queries))
+    }
+
+    /// Same as [`Query::and`] but returns [`Error::InvalidQuery`] when one of
+    /// `queries` is not a valid query.
+    pub fn try_and(queries: Vec<String>) -> Result<String, Error> {
+        Ok(QueryExpr::And(parse_all(&queries)?).to_string())
     }
 
     /// Specify which attributes should be returned by the API call.
     pub fn select(attributes: QueryValue) -> String {
-        Q::new("select", None::<&str>.into(), attributes).to_string()
+        QueryExpr::Select(query_values(attributes).iter().map(string_value).collect()).to_string()
     }
 
     /// Sort results by [attribute] ascending.
@@ -239,7 +551,7 @@ impl Query {
     ///     );
     /// ```
     pub fn order_asc(attribute: QueryValue) -> String {
-        Q::new("orderAsc", attribute, None::<&str>.into()).to_string()
+        QueryExpr::OrderAsc(attribute_name(attribute)).to_string()
     }
 
     /// Sort results by [attribute] descending.
@@ -250,7 +562,18 @@ impl Query {
     ///     );
     /// ```
     pub fn order_desc(attribute: QueryValue) -> String {
-        Q::new("orderDesc", attribute, None::<&str>.into()).to_string()
+        QueryExpr::OrderDesc(attribute_name(attribute)).to_string()
+    }
+
+    /// Sort results randomly.
+    /// ```
+    /// assert_eq!(
+    ///         unofficial_appwrite::query::Query::order_random(),
+    ///         r#"{"method":"orderRandom"}"#
+    ///     );
+    /// ```
+    pub fn order_random() -> String {
+        QueryExpr::OrderRandom.to_string()
     }
 
     /// Return results before [id].
@@ -264,12 +587,7 @@ impl Query {
     ///     );
     /// ```
     pub fn cursor_before(id: QueryValue) -> String {
-        Q::new(
-            "cursorBefore",
-            None::<&str>.into(),
-            id.convert_to_array_if_not_array(),
-        )
-        .to_string()
+        QueryExpr::CursorBefore(first(id).map(|v| string_value(&v)).unwrap_or_default()).to_string()
     }
 
     /// Return results after [id].
@@ -283,15 +601,13 @@ impl Query {
     ///     );
     /// ```
     pub fn cursor_after(id: QueryValue) -> String {
-        Q::new(
-            "cursorAfter",
-            None::<&str>.into(),
-            id.convert_to_array_if_not_array(),
-        )
-        .to_string()
+        QueryExpr::CursorAfter(first(id).map(|v| string_value(&v)).unwrap_or_default()).to_string()
     }
 
     /// Return only [limit] results.
+    ///
+    /// A negative or non-numeric `limit` becomes `0`, use [`Query::try_limit`]
+    /// to reject it instead.
     /// ```
     /// assert_eq!(
     ///         unofficial_appwrite::query::Query::limit(25.into()),
@@ -299,18 +615,25 @@ impl Query {
     ///     );
     /// ```
     pub fn limit(limit: QueryValue) -> String {
-        Q::new(
-            "limit",
-            None::<&str>.into(),
-            limit.convert_to_array_if_not_array(),
-        )
-        .to_string()
+        QueryExpr::Limit(coerce_count(limit)).to_string()
+    }
+
+    /// Same as [`Query::limit`] but returns [`Error::InvalidQuery`] unless
+    /// `limit` is a non-negative integer.
+    /// ```
+    /// use unofficial_appwrite::query::Query;
+    /// assert!(Query::try_limit(25.into()).is_ok());
+    /// assert!(Query::try_limit((-1).into()).is_err());
+    /// ```
+    pub fn try_limit(limit: QueryValue) -> Result<String, Error> {
+        Ok(count("limit", limit)?.to_string())
     }
 
     /// Return results from [offset].
     ///
     /// Refer to the [Offset Pagination](https://appwrite.io/docs/pagination#offset-pagination)
-    /// docs for more information.
+    /// docs for more information. A negative or non-numeric `offset` becomes
+    /// `0`, use [`Query::try_offset`] to reject it instead.
     /// ```
     /// assert_eq!(
     ///         unofficial_appwrite::query::Query::offset(0.into()),
@@ -318,19 +641,110 @@ impl Query {
     ///     );
     /// ```
     pub fn offset(offset: QueryValue) -> String {
-        Q::new(
-            "offset",
-            None::<&str>.into(),
-            offset.convert_to_array_if_not_array(),
-        )
-        .to_string()
+        QueryExpr::Offset(coerce_count(offset)).to_string()
+    }
+
+    /// Same as [`Query::offset`] but returns [`Error::InvalidQuery`] unless
+    /// `offset` is a non-negative integer.
+    pub fn try_offset(offset: QueryValue) -> Result<String, Error> {
+        Ok(count("offset", offset)?.to_string())
+    }
+}
+
+fn filter(op: FilterOp, attribute: QueryValue, values: QueryValue) -> String {
+    QueryExpr::filter(op, attribute_name(attribute), query_values(values)).to_string()
+}
+
+fn date_filter<Tz: TimeZone>(op: FilterOp, attribute: &str, values: &[DateTime<Tz>]) -> String {
+    let values = values
+        .iter()
+        .map(|value| json!(QueryValue::from(value.clone())))
+        .collect();
+    QueryExpr::filter(op, attribute, values).to_string()
+}
+
+fn distance_filter(
+    op: FilterOp,
+    attribute: QueryValue,
+    values: QueryValue,
+    distance: f64,
+    meters: bool,
+) -> String {
+    let values = vec![json!([values, distance, meters])];
+    QueryExpr::filter(op, attribute_name(attribute), values).to_string()
+}
+
+fn geometry_filter(op: FilterOp, attribute: QueryValue, values: QueryValue) -> String {
+    QueryExpr::filter(op, attribute_name(attribute), vec![json!(values)]).to_string()
+}
+
+fn parse_all(queries: &[String]) -> Result<Vec<QueryExpr>, Error> {
+    queries.iter().map(|q| QueryExpr::parse(q)).collect()
+}
+
+/// `or`/`and` of queries which did not parse, left for the server to reject.
+fn raw_logical(method: &str, queries: &[String]) -> String {
+    let values: Vec<Value> = queries
+        .iter()
+        .map(|q| serde_json::from_str(q).unwrap_or_else(|_| json!(q)))
+        .collect();
+    json!({"method": method, "values": values}).to_string()
+}
+
+fn first(values: QueryValue) -> Option<Value> {
+    query_values(values).into_iter().next()
+}
+
+fn string_value(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        value => value.to_string(),
     }
 }
 
+/// `limit` or `offset` query, validated like a parsed one.
+fn count(method: &str, values: QueryValue) -> Result<QueryExpr, Error> {
+    QueryExpr::from_value(&json!({"method": method, "values": query_values(values)}))
+}
+
+fn coerce_count(values: QueryValue) -> u64 {
+    first(values)
+        .and_then(|v| v.as_u64().or_else(|| v.as_f64().map(|f| f.max(0.0) as u64)))
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_try_helpers_reject_invalid_input() {
+        assert_eq!(
+            Query::try_limit(25.into()).unwrap(),
+            Query::limit(25.into())
+        );
+        assert!(matches!(
+            Query::try_limit((-5).into()),
+            Err(Error::InvalidQuery(message)) if message.contains("limit needs a non-negative integer")
+        ));
+        assert!(Query::try_offset("ten".into()).is_err());
+        assert_eq!(
+            Query::limit(25.0.into()),
+            r#"{"method":"limit","values":[25]}"#
+        );
+
+        let valid = Query::equal("title".into(), "a".into());
+        assert_eq!(
+            Query::try_or(vec![valid.clone()]).unwrap(),
+            Query::or(vec![valid.clone()])
+        );
+        assert!(Query::try_and(vec![valid.clone(), "not json".to_string()]).is_err());
+        assert_eq!(
+            Query::and(vec!["not json".to_string()]),
+            r#"{"method":"and","values":["not json"]}"#
+        );
+    }
+
     #[test]
     fn test_all_query_at_once() {
         assert_eq!(
@@ -345,13 +759,10 @@ mod tests {
             r#"{"method":"and","values":[{"attribute":"size","method":"lessThan","values":[10]},{"attribute":"size","method":"greaterThan","values":[5]}]}"#
         );
         assert_eq!(
-            Query::or(
-                vec![
-                    Query::less_than("size".into(), 5.into()),
-                    Query::greater_than("size".into(), 10.into())
-                ]
-                .into()
-            ),
+            Query::or(vec![
+                Query::less_than("size".into(), 5.into()),
+                Query::greater_than("size".into(), 10.into())
+            ]),
             r#"{"method":"or","values":[{"attribute":"size","method":"lessThan","values":[5]},{"attribute":"size","method":"greaterThan","values":[10]}]}"#
         );
         assert_eq!(
@@ -436,6 +847,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_new_operators_parse() {
+        let date = chrono::Utc::now();
+        let point: QueryValue = vec![40.7, -74.0].into();
+        for query in [
+            Query::not_contains("tags".into(), "draft".into()),
+            Query::not_search("text".into(), "spam".into()),
+            Query::not_between("price".into(), 5.into(), 10.into()),
+            Query::not_starts_with("name".into(), "tmp_".into()),
+            Query::not_ends_with("name".into(), ".bak".into()),
+            Query::created_before(date),
+            Query::created_after(date),
+            Query::created_between(date, date),
+            Query::updated_before(date),
+            Query::updated_after(date),
+            Query::updated_between(date, date),
+            Query::order_random(),
+            Query::distance_less_than("location".into(), point.clone(), 10.0, true),
+            Query::intersects("area".into(), point.clone()),
+            Query::crosses("area".into(), point.clone()),
+            Query::overlaps("area".into(), point.clone()),
+            Query::touches("area".into(), point),
+        ] {
+            assert!(QueryExpr::parse(&query).is_ok(), "{}", query);
+        }
+    }
+
     // #[test]
     // fn test_select_query() {
     //     let query = Query::select(vec!["name", "age"]);
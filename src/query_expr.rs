@@ -0,0 +1,525 @@
+//! # Query expressions
+//!
+//! Typed form of the queries sent in the `queries` param of list endpoints.
+//! A [`QueryExpr`] renders to the JSON wire format produced by
+//! [`Query`](crate::query::Query) and parses back from it, so queries can be
+//! inspected, combined and checked before they are sent.
+//! ```
+//! use unofficial_appwrite::query_expr::{FilterOp, QueryExpr};
+//!
+//! let queries = vec![
+//!     QueryExpr::or(vec![
+//!         QueryExpr::filter(FilterOp::LessThan, "size", vec![5.into()]),
+//!         QueryExpr::filter(FilterOp::GreaterThan, "size", vec![10.into()]),
+//!     ]),
+//!     QueryExpr::Limit(25),
+//! ];
+//! let rendered = QueryExpr::render_all(&queries).unwrap();
+//! assert_eq!(rendered[1], r#"{"method":"limit","values":[25]}"#);
+//! assert_eq!(QueryExpr::parse(&rendered[0]).unwrap(), queries[0]);
+//!
+//! assert!(QueryExpr::render_all(&[QueryExpr::Limit(1), QueryExpr::Limit(2)]).is_err());
+//! ```
+use std::{fmt::Display, str::FromStr};
+
+use serde_json::{json, Map, Value};
+
+use crate::{
+    error::Error,
+    query_value::{Query as Q, QueryValue},
+};
+
+/// Number of values a filter operator takes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arity {
+    None,
+    One,
+    Two,
+    AtLeastOne,
+}
+
+/// Comparison applied to an attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FilterOp {
+    Equal,
+    NotEqual,
+    LessThan,
+    LessThanEqual,
+    GreaterThan,
+    GreaterThanEqual,
+    Search,
+    IsNull,
+    IsNotNull,
+    Between,
+    StartsWith,
+    EndsWith,
+    Contains,
+    NotContains,
+    NotSearch,
+    NotBetween,
+    NotStartsWith,
+    NotEndsWith,
+    DistanceEqual,
+    DistanceNotEqual,
+    DistanceGreaterThan,
+    DistanceLessThan,
+    Intersects,
+    NotIntersects,
+    Crosses,
+    NotCrosses,
+    Overlaps,
+    NotOverlaps,
+    Touches,
+    NotTouches,
+}
+
+impl FilterOp {
+    pub const ALL: [FilterOp; 30] = [
+        FilterOp::Equal,
+        FilterOp::NotEqual,
+        FilterOp::LessThan,
+        FilterOp::LessThanEqual,
+        FilterOp::GreaterThan,
+        FilterOp::GreaterThanEqual,
+        FilterOp::Search,
+        FilterOp::IsNull,
+        FilterOp::IsNotNull,
+        FilterOp::Between,
+        FilterOp::StartsWith,
+        FilterOp::EndsWith,
+        FilterOp::Contains,
+        FilterOp::NotContains,
+        FilterOp::NotSearch,
+        FilterOp::NotBetween,
+        FilterOp::NotStartsWith,
+        FilterOp::NotEndsWith,
+        FilterOp::DistanceEqual,
+        FilterOp::DistanceNotEqual,
+        FilterOp::DistanceGreaterThan,
+        FilterOp::DistanceLessThan,
+        FilterOp::Intersects,
+        FilterOp::NotIntersects,
+        FilterOp::Crosses,
+        FilterOp::NotCrosses,
+        FilterOp::Overlaps,
+        FilterOp::NotOverlaps,
+        FilterOp::Touches,
+        FilterOp::NotTouches,
+    ];
+
+    /// Method name used on the wire.
+    pub fn method(&self) -> &'static str {
+        match self {
+            FilterOp::Equal => "equal",
+            FilterOp::NotEqual => "notEqual",
+            FilterOp::LessThan => "lessThan",
+            FilterOp::LessThanEqual => "lessThanEqual",
+            FilterOp::GreaterThan => "greaterThan",
+            FilterOp::GreaterThanEqual => "greaterThanEqual",
+            FilterOp::Search => "search",
+            FilterOp::IsNull => "isNull",
+            FilterOp::IsNotNull => "isNotNull",
+            FilterOp::Between => "between",
+            FilterOp::StartsWith => "startsWith",
+            FilterOp::EndsWith => "endsWith",
+            FilterOp::Contains => "contains",
+            FilterOp::NotContains => "notContains",
+            FilterOp::NotSearch => "notSearch",
+            FilterOp::NotBetween => "notBetween",
+            FilterOp::NotStartsWith => "notStartsWith",
+            FilterOp::NotEndsWith => "notEndsWith",
+            FilterOp::DistanceEqual => "distanceEqual",
+            FilterOp::DistanceNotEqual => "distanceNotEqual",
+            FilterOp::DistanceGreaterThan => "distanceGreaterThan",
+            FilterOp::DistanceLessThan => "distanceLessThan",
+            FilterOp::Intersects => "intersects",
+            FilterOp::NotIntersects => "notIntersects",
+            FilterOp::Crosses => "crosses",
+            FilterOp::NotCrosses => "notCrosses",
+            FilterOp::Overlaps => "overlaps",
+            FilterOp::NotOverlaps => "notOverlaps",
+            FilterOp::Touches => "touches",
+            FilterOp::NotTouches => "notTouches",
+        }
+    }
+
+    pub fn from_method(method: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|op| op.method() == method)
+    }
+
+    pub fn arity(&self) -> Arity {
+        match self {
+            FilterOp::IsNull | FilterOp::IsNotNull => Arity::None,
+            FilterOp::Between | FilterOp::NotBetween => Arity::Two,
+            FilterOp::Equal | FilterOp::NotEqual | FilterOp::Contains | FilterOp::NotContains => {
+                Arity::AtLeastOne
+            }
+            _ => Arity::One,
+        }
+    }
+}
+
+/// A single Appwrite query.
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryExpr {
+    Filter {
+        op: FilterOp,
+        attribute: String,
+        values: Vec<Value>,
+    },
+    And(Vec<QueryExpr>),
+    Or(Vec<QueryExpr>),
+    OrderAsc(String),
+    OrderDesc(String),
+    OrderRandom,
+    CursorBefore(String),
+    CursorAfter(String),
+    Limit(u64),
+    Offset(u64),
+    Select(Vec<String>),
+}
+
+impl QueryExpr {
+    pub fn filter(op: FilterOp, attribute: impl Into<String>, values: Vec<Value>) -> Self {
+        QueryExpr::Filter {
+            op,
+            attribute: attribute.into(),
+            values,
+        }
+    }
+
+    pub fn and(queries: Vec<QueryExpr>) -> Self {
+        QueryExpr::And(queries)
+    }
+
+    pub fn or(queries: Vec<QueryExpr>) -> Self {
+        QueryExpr::Or(queries)
+    }
+
+    /// Method name used on the wire.
+    pub fn method(&self) -> &'static str {
+        match self {
+            QueryExpr::Filter { op, .. } => op.method(),
+            QueryExpr::And(_) => "and",
+            QueryExpr::Or(_) => "or",
+            QueryExpr::OrderAsc(_) => "orderAsc",
+            QueryExpr::OrderDesc(_) => "orderDesc",
+            QueryExpr::OrderRandom => "orderRandom",
+            QueryExpr::CursorBefore(_) => "cursorBefore",
+            QueryExpr::CursorAfter(_) => "cursorAfter",
+            QueryExpr::Limit(_) => "limit",
+            QueryExpr::Offset(_) => "offset",
+            QueryExpr::Select(_) => "select",
+        }
+    }
+
+    fn attribute(&self) -> Option<&str> {
+        match self {
+            QueryExpr::Filter { attribute, .. }
+            | QueryExpr::OrderAsc(attribute)
+            | QueryExpr::OrderDesc(attribute) => Some(attribute.as_str()),
+            _ => None,
+        }
+    }
+
+    fn values(&self) -> Option<Value> {
+        match self {
+            QueryExpr::Filter { op, values, .. } => match op.arity() {
+                Arity::None if values.is_empty() => None,
+                _ => Some(Value::Array(values.clone())),
+            },
+            QueryExpr::And(queries) | QueryExpr::Or(queries) => {
+                Some(queries.iter().map(QueryExpr::to_value).collect())
+            }
+            QueryExpr::OrderAsc(_) | QueryExpr::OrderDesc(_) | QueryExpr::OrderRandom => None,
+            QueryExpr::CursorBefore(id) | QueryExpr::CursorAfter(id) => Some(json!([id])),
+            QueryExpr::Limit(n) | QueryExpr::Offset(n) => Some(json!([n])),
+            QueryExpr::Select(attributes) => Some(json!(attributes)),
+        }
+    }
+
+    /// JSON object of the query.
+    pub fn to_value(&self) -> Value {
+        let mut map = Map::new();
+        map.insert("method".to_string(), json!(self.method()));
+        if let Some(attribute) = self.attribute() {
+            map.insert("attribute".to_string(), json!(attribute));
+        }
+        if let Some(values) = self.values() {
+            map.insert("values".to_string(), values);
+        }
+        Value::Object(map)
+    }
+
+    /// Read a query from its JSON object, without validating it.
+    pub fn from_value(value: &Value) -> Result<Self, Error> {
+        let invalid = |message: String| Error::InvalidQuery(format!("{}: {}", message, value));
+        let method = value
+            .get("method")
+            .and_then(Value::as_str)
+            .ok_or_else(|| invalid("missing method".to_string()))?;
+        let attribute = || {
+            value
+                .get("attribute")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .ok_or_else(|| invalid(format!("{} needs an attribute", method)))
+        };
+        let values = match value.get("values") {
+            None | Some(Value::Null) => Vec::new(),
+            Some(Value::Array(values)) => values.clone(),
+            Some(_) => return Err(invalid("values must be an array".to_string())),
+        };
+        let first = || {
+            values
+                .first()
+                .ok_or_else(|| invalid(format!("{} needs a value", method)))
+        };
+        let count = || {
+            first()?
+                .as_u64()
+                .ok_or_else(|| invalid(format!("{} needs a non-negative integer", method)))
+        };
+        let string = |value: &Value| {
+            value
+                .as_str()
+                .map(str::to_string)
+                .ok_or_else(|| invalid(format!("{} needs string values", method)))
+        };
+
+        if let Some(op) = FilterOp::from_method(method) {
+            return Ok(QueryExpr::Filter {
+                op,
+                attribute: attribute()?,
+                values,
+            });
+        }
+        Ok(match method {
+            "and" | "or" => {
+                let queries = values
+                    .iter()
+                    .map(QueryExpr::from_value)
+                    .collect::<Result<Vec<_>, _>>()?;
+                if method == "and" {
+                    QueryExpr::And(queries)
+                } else {
+                    QueryExpr::Or(queries)
+                }
+            }
+            "orderAsc" => QueryExpr::OrderAsc(attribute()?),
+            "orderDesc" => QueryExpr::OrderDesc(attribute()?),
+            "orderRandom" => QueryExpr::OrderRandom,
+            "cursorBefore" => QueryExpr::CursorBefore(string(first()?)?),
+            "cursorAfter" => QueryExpr::CursorAfter(string(first()?)?),
+            "limit" => QueryExpr::Limit(count()?),
+            "offset" => QueryExpr::Offset(count()?),
+            "select" => QueryExpr::Select(values.iter().map(string).collect::<Result<_, _>>()?),
+            _ => return Err(invalid(format!("unknown method `{}`", method))),
+        })
+    }
+
+    /// Parse and validate a query in the JSON wire format.
+    pub fn parse(query: &str) -> Result<Self, Error> {
+        let value: Value = serde_json::from_str(query)
+            .map_err(|err| Error::InvalidQuery(format!("{}: {}", err, query)))?;
+        let expr = Self::from_value(&value)?;
+        expr.validate()?;
+        Ok(expr)
+    }
+
+    /// Check the values of this query and of the queries nested in it.
+    pub fn validate(&self) -> Result<(), Error> {
+        let invalid = |message: String| Err(Error::InvalidQuery(message));
+        match self {
+            QueryExpr::Filter {
+                op,
+                attribute,
+                values,
+            } => {
+                if attribute.is_empty() {
+                    return invalid(format!("{} needs an attribute", op.method()));
+                }
+                let ok = match op.arity() {
+                    Arity::None => values.is_empty(),
+                    Arity::One => values.len() == 1,
+                    Arity::Two => values.len() == 2,
+                    Arity::AtLeastOne => !values.is_empty(),
+                };
+                if !ok {
+                    return invalid(format!(
+                        "{} on `{}` takes {}, got {}",
+                        op.method(),
+                        attribute,
+                        match op.arity() {
+                            Arity::None => "no values",
+                            Arity::One => "one value",
+                            Arity::Two => "two values",
+                            Arity::AtLeastOne => "at least one value",
+                        },
+                        values.len()
+                    ));
+                }
+                Ok(())
+            }
+            QueryExpr::And(queries) | QueryExpr::Or(queries) => {
+                if queries.is_empty() {
+                    return invalid(format!("{} needs at least one query", self.method()));
+                }
+                for query in queries {
+                    if !matches!(
+                        query,
+                        QueryExpr::Filter { .. } | QueryExpr::And(_) | QueryExpr::Or(_)
+                    ) {
+                        return invalid(format!(
+                            "{} can only combine filters, got {}",
+                            self.method(),
+                            query.method()
+                        ));
+                    }
+                    query.validate()?;
+                }
+                Ok(())
+            }
+            QueryExpr::OrderAsc(attribute) | QueryExpr::OrderDesc(attribute)
+                if attribute.is_empty() =>
+            {
+                invalid(format!("{} needs an attribute", self.method()))
+            }
+            QueryExpr::CursorBefore(id) | QueryExpr::CursorAfter(id) if id.is_empty() => {
+                invalid(format!("{} needs a document id", self.method()))
+            }
+            QueryExpr::Select(attributes) if attributes.is_empty() => {
+                invalid("select needs at least one attribute".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Validate a set of queries sent together: each query on its own, plus at
+    /// most one `limit`, one `offset`, one cursor and one `select`.
+    pub fn validate_all(queries: &[QueryExpr]) -> Result<(), Error> {
+        let mut seen = [0usize; 4];
+        for query in queries {
+            query.validate()?;
+            let slot = match query {
+                QueryExpr::Limit(_) => 0,
+                QueryExpr::Offset(_) => 1,
+                QueryExpr::CursorBefore(_) | QueryExpr::CursorAfter(_) => 2,
+                QueryExpr::Select(_) => 3,
+                _ => continue,
+            };
+            seen[slot] += 1;
+            if seen[slot] > 1 {
+                return Err(Error::InvalidQuery(format!(
+                    "only one {} query is allowed",
+                    ["limit", "offset", "cursor", "select"][slot]
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Validate `queries` and render them for the `queries` param.
+    pub fn render_all(queries: &[QueryExpr]) -> Result<Vec<String>, Error> {
+        Self::validate_all(queries)?;
+        Ok(queries.iter().map(QueryExpr::to_string).collect())
+    }
+}
+
+impl Display for QueryExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let attribute = self.attribute().map(QueryValue::from).into();
+        let values = self.values().map(QueryValue::JsonObject).into();
+        Display::fmt(&Q::new(self.method(), attribute, values), f)
+    }
+}
+
+impl FromStr for QueryExpr {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// Attribute name of a [`QueryValue`], empty unless it is a string.
+pub(crate) fn attribute_name(attribute: QueryValue) -> String {
+    match attribute {
+        QueryValue::String(attribute) => attribute,
+        _ => String::new(),
+    }
+}
+
+/// Values of a [`QueryValue`], a scalar becomes a single value.
+pub(crate) fn query_values(values: QueryValue) -> Vec<Value> {
+    match values {
+        QueryValue::Null => Vec::new(),
+        QueryValue::Array(values) => values.into_iter().map(|v| json!(v)).collect(),
+        QueryValue::JsonObject(Value::Array(values)) => values,
+        other => vec![json!(other)],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() -> Result<(), Error> {
+        let queries = vec![
+            QueryExpr::filter(FilterOp::Between, "price", vec![json!(5), json!(10)]),
+            QueryExpr::filter(FilterOp::IsNull, "name", vec![]),
+            QueryExpr::and(vec![
+                QueryExpr::filter(FilterOp::Equal, "title", vec![json!("a"), json!("b")]),
+                QueryExpr::or(vec![QueryExpr::filter(
+                    FilterOp::Search,
+                    "text",
+                    vec![json!("key words")],
+                )]),
+            ]),
+            QueryExpr::OrderDesc("$createdAt".into()),
+            QueryExpr::OrderRandom,
+            QueryExpr::filter(
+                FilterOp::DistanceLessThan,
+                "location",
+                vec![json!([[40.7, -74.0], 1000, true])],
+            ),
+            QueryExpr::filter(FilterOp::NotBetween, "price", vec![json!(5), json!(10)]),
+            QueryExpr::CursorAfter("62a7...f620".into()),
+            QueryExpr::Limit(25),
+            QueryExpr::Offset(0),
+            QueryExpr::Select(vec!["name".into(), "age".into()]),
+        ];
+        for (query, rendered) in queries.iter().zip(QueryExpr::render_all(&queries)?) {
+            assert_eq!(&QueryExpr::parse(&rendered)?, query);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_queries() {
+        let invalid = [
+            r#"{"method":"between","attribute":"price","values":[5]}"#,
+            r#"{"method":"notBetween","attribute":"price","values":[5, 6, 7]}"#,
+            r#"{"method":"intersects","attribute":"area","values":[]}"#,
+            r#"{"method":"isNull","attribute":"name","values":[1]}"#,
+            r#"{"method":"equal","attribute":"title","values":[]}"#,
+            r#"{"method":"limit","values":[-1]}"#,
+            r#"{"method":"or","values":[{"method":"limit","values":[1]}]}"#,
+            r#"{"method":"nearby","attribute":"name","values":[1]}"#,
+            r#"{"method":"equal","values":[1]}"#,
+        ];
+        for query in invalid {
+            assert!(
+                matches!(QueryExpr::parse(query), Err(Error::InvalidQuery(_))),
+                "{}",
+                query
+            );
+        }
+        assert!(QueryExpr::validate_all(&[
+            QueryExpr::CursorAfter("a".into()),
+            QueryExpr::CursorBefore("b".into()),
+        ])
+        .is_err());
+    }
+}
@@ -1,6 +1,35 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, SecondsFormat, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use uuid::Uuid;
 
+/// Value of a query.
+///
+/// Converts from integers and floats of every width, strings, booleans,
+/// `Option`, vectors, slices and arrays of convertible values, chrono dates,
+/// [`Uuid`] and `(x, y)` coordinate pairs.
+/// ```
+/// use chrono::NaiveDate;
+/// use unofficial_appwrite::query::Query;
+///
+/// assert_eq!(
+///         Query::greater_than("count".into(), 3u8.into()),
+///         r#"{"method":"greaterThan","attribute":"count","values":[3]}"#
+///     );
+/// assert_eq!(
+///         Query::equal("day".into(), vec![NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()].into()),
+///         r#"{"method":"equal","attribute":"day","values":["2024-01-01T00:00:00.000+00:00"]}"#
+///     );
+/// assert_eq!(
+///         Query::intersects("area".into(), (40.7, -74.0).into()),
+///         r#"{"method":"intersects","attribute":"area","values":[[40.7,-74.0]]}"#
+///     );
+/// ```
+/// Types without a conversion are rejected at compile time.
+/// ```compile_fail
+/// let value: unofficial_appwrite::query_value::QueryValue =
+///     std::time::Duration::from_secs(1).into();
+/// ```
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[allow(missing_docs)]
 #[serde(untagged)]
@@ -14,24 +43,48 @@ pub enum QueryValue {
     JsonObject(serde_json::Value),
 }
 
-impl QueryValue {
-    pub(crate) fn convert_to_array_if_not_array(self) -> Self {
-        match self {
-            QueryValue::Array(_) => self, // Already an array, use as is
-            other => vec![other].into(),  // Wrap in an array
+impl<T: Into<QueryValue>> From<Option<T>> for QueryValue {
+    fn from(v: Option<T>) -> QueryValue {
+        v.map(|v| v.into()).unwrap_or(QueryValue::Null)
+    }
+}
+
+macro_rules! from_int {
+    ($($t:ty),*) => {$(
+        impl From<$t> for QueryValue {
+            fn from(v: $t) -> QueryValue {
+                QueryValue::Int64(i64::from(v))
+            }
         }
+    )*};
+}
+
+from_int!(i8, i16, i32, i64, u8, u16, u32);
+
+impl From<isize> for QueryValue {
+    fn from(v: isize) -> QueryValue {
+        QueryValue::Int64(v as i64)
     }
 }
 
-impl<T: Into<QueryValue>> From<Option<T>> for QueryValue {
-    fn from(v: Option<T>) -> QueryValue {
-        v.map(|v| v.into()).unwrap_or(QueryValue::Null)
+/// Values above `i64::MAX` are kept exact as a JSON number.
+impl From<u64> for QueryValue {
+    fn from(v: u64) -> QueryValue {
+        i64::try_from(v)
+            .map(QueryValue::Int64)
+            .unwrap_or_else(|_| QueryValue::JsonObject(json!(v)))
+    }
+}
+
+impl From<usize> for QueryValue {
+    fn from(v: usize) -> QueryValue {
+        QueryValue::from(v as u64)
     }
 }
 
-impl From<i64> for QueryValue {
-    fn from(v: i64) -> QueryValue {
-        QueryValue::Int64(v)
+impl From<f32> for QueryValue {
+    fn from(v: f32) -> QueryValue {
+        QueryValue::Float64(f64::from(v))
     }
 }
 
@@ -59,45 +112,73 @@ impl From<String> for QueryValue {
     }
 }
 
-impl From<Vec<QueryValue>> for QueryValue {
-    fn from(v: Vec<QueryValue>) -> QueryValue {
-        QueryValue::Array(v)
+impl From<&String> for QueryValue {
+    fn from(v: &String) -> QueryValue {
+        QueryValue::String(v.clone())
+    }
+}
+
+impl<T: Into<QueryValue>> From<Vec<T>> for QueryValue {
+    fn from(v: Vec<T>) -> QueryValue {
+        QueryValue::Array(v.into_iter().map(Into::into).collect())
     }
 }
 
-// impl From<&[QueryValue]> for QueryValue {
-//     fn from(v: &[QueryValue]) -> Self {
-//         QueryValue::Array(v.to_vec())
-//     }
-// }
+impl<T: Into<QueryValue> + Clone> From<&[T]> for QueryValue {
+    fn from(v: &[T]) -> QueryValue {
+        QueryValue::Array(v.iter().cloned().map(Into::into).collect())
+    }
+}
 
-impl From<Vec<String>> for QueryValue {
-    fn from(v: Vec<String>) -> Self {
-        QueryValue::Array(v.into_iter().map(QueryValue::from).collect())
+impl<T: Into<QueryValue>, const N: usize> From<[T; N]> for QueryValue {
+    fn from(v: [T; N]) -> QueryValue {
+        QueryValue::Array(v.into_iter().map(Into::into).collect())
     }
 }
 
-impl From<Vec<&str>> for QueryValue {
-    fn from(v: Vec<&str>) -> Self {
-        QueryValue::Array(v.into_iter().map(QueryValue::from).collect())
+/// Datetime in the ISO 8601 format used by Appwrite, e.g.
+/// `2024-01-01T00:00:00.000+00:00`.
+fn format_datetime<Tz: TimeZone>(v: &DateTime<Tz>) -> String {
+    v.with_timezone(&Utc)
+        .to_rfc3339_opts(SecondsFormat::Millis, false)
+}
+
+impl<Tz: TimeZone> From<DateTime<Tz>> for QueryValue {
+    fn from(v: DateTime<Tz>) -> QueryValue {
+        QueryValue::String(format_datetime(&v))
     }
 }
 
-impl From<Vec<i64>> for QueryValue {
-    fn from(v: Vec<i64>) -> Self {
-        QueryValue::Array(v.into_iter().map(QueryValue::from).collect())
+/// Read as UTC.
+impl From<NaiveDateTime> for QueryValue {
+    fn from(v: NaiveDateTime) -> QueryValue {
+        QueryValue::from(v.and_utc())
     }
 }
 
-impl From<Vec<f64>> for QueryValue {
-    fn from(v: Vec<f64>) -> Self {
-        QueryValue::Array(v.into_iter().map(QueryValue::from).collect())
+/// Midnight UTC of the date.
+impl From<NaiveDate> for QueryValue {
+    fn from(v: NaiveDate) -> QueryValue {
+        QueryValue::from(v.and_time(NaiveTime::MIN))
     }
 }
 
-impl From<Vec<bool>> for QueryValue {
-    fn from(v: Vec<bool>) -> Self {
-        QueryValue::Array(v.into_iter().map(QueryValue::from).collect())
+impl From<Uuid> for QueryValue {
+    fn from(v: Uuid) -> QueryValue {
+        QueryValue::String(v.to_string())
+    }
+}
+
+/// Coordinate pair of a point, e.g. `[longitude, latitude]`.
+impl From<(f64, f64)> for QueryValue {
+    fn from((x, y): (f64, f64)) -> QueryValue {
+        QueryValue::Array(vec![x.into(), y.into()])
+    }
+}
+
+impl From<(f32, f32)> for QueryValue {
+    fn from((x, y): (f32, f32)) -> QueryValue {
+        QueryValue::Array(vec![x.into(), y.into()])
     }
 }
 
@@ -135,7 +216,45 @@ impl Query {
             values: val,
         }
     }
-    pub fn to_string(&self) -> String {
-        serde_json::to_string(self).unwrap()
+}
+
+impl std::fmt::Display for Query {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            serde_json::to_string(self).map_err(|_| std::fmt::Error)?
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(json!(QueryValue::from(7u16)), json!(7));
+        assert_eq!(json!(QueryValue::from(-7i8)), json!(-7));
+        assert_eq!(json!(QueryValue::from(u64::MAX)), json!(u64::MAX));
+        assert_eq!(json!(QueryValue::from(1.5f32)), json!(1.5));
+        assert_eq!(json!(QueryValue::from(&[1u32, 2][..])), json!([1, 2]));
+        assert_eq!(json!(QueryValue::from(["a", "b"])), json!(["a", "b"]));
+        assert_eq!(
+            json!(QueryValue::from(vec![(1.0, 2.0), (3.0, 4.0)])),
+            json!([[1.0, 2.0], [3.0, 4.0]])
+        );
+        assert_eq!(
+            json!(QueryValue::from(Uuid::nil())),
+            json!("00000000-0000-0000-0000-000000000000")
+        );
+        let date = NaiveDate::from_ymd_opt(2024, 2, 29)
+            .unwrap()
+            .and_hms_milli_opt(13, 5, 9, 42)
+            .unwrap();
+        assert_eq!(
+            json!(QueryValue::from(date)),
+            json!("2024-02-29T13:05:09.042+00:00")
+        );
     }
 }
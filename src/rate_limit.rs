@@ -0,0 +1,322 @@
+//! # Rate limit
+//!
+//! Client-side throttling which follows the `X-RateLimit-*` headers sent by
+//! Appwrite, so concurrent tasks wait for the window to reset instead of
+//! firing requests that come back as `429`.
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use reqwest::Response;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::{enumm::HttpMethod, response_meta::RateLimit, retry::retry_after};
+
+/// Wait for a bucket reported empty without a reset time.
+const EMPTY_BUCKET_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Maps a request to the endpoint group sharing one token bucket.
+pub type GroupBy = Arc<dyn Fn(HttpMethod, &str) -> String + Send + Sync>;
+
+/// Group requests by method and service, e.g. `POST /databases`.
+pub fn group_by_service(method: HttpMethod, path: &str) -> String {
+    let service = path.trim_start_matches('/').split('/').next().unwrap_or("");
+    format!("{:?} /{}", method, service)
+}
+
+/// Settings of the client-side rate limiter.
+#[derive(Clone)]
+pub struct RateLimiterConfig {
+    /// Maximum number of requests in flight at once, across all groups.
+    pub max_in_flight: Option<usize>,
+    /// How requests are grouped into buckets, see [`group_by_service`].
+    pub group_by: GroupBy,
+}
+
+impl Default for RateLimiterConfig {
+    fn default() -> Self {
+        Self {
+            max_in_flight: None,
+            group_by: Arc::new(group_by_service),
+        }
+    }
+}
+
+impl Debug for RateLimiterConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RateLimiterConfig")
+            .field("max_in_flight", &self.max_in_flight)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Bucket {
+    /// `X-RateLimit-Limit`, the tokens of a fresh window.
+    limit: Option<u64>,
+    remaining: Option<u64>,
+    reset: Option<DateTime<Utc>>,
+}
+
+/// Token bucket per endpoint group plus a global in-flight cap.
+#[derive(Debug)]
+pub struct RateLimiter {
+    config: RateLimiterConfig,
+    buckets: Mutex<HashMap<String, Bucket>>,
+    in_flight: Option<Arc<Semaphore>>,
+}
+
+/// Held while a request is in flight.
+#[derive(Debug)]
+pub struct RateLimitPermit {
+    group: String,
+    _in_flight: Option<OwnedSemaphorePermit>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimiterConfig) -> Self {
+        Self {
+            in_flight: config
+                .max_in_flight
+                .map(|max| Arc::new(Semaphore::new(max.max(1)))),
+            buckets: Mutex::new(HashMap::new()),
+            config,
+        }
+    }
+
+    /// Wait until a request to `path` may be sent and reserve a token for it.
+    ///
+    /// The in-flight slot is only taken once the bucket of the group has a
+    /// token, so a throttled group does not hold slots other groups could use.
+    pub async fn acquire(&self, method: HttpMethod, path: &str) -> RateLimitPermit {
+        let group = (self.config.group_by)(method, path);
+        loop {
+            while let Some(wait) = self.wait_time(&group, Utc::now()) {
+                tokio::time::sleep(wait).await;
+            }
+            let in_flight = match self.in_flight.clone() {
+                Some(semaphore) => semaphore.acquire_owned().await.ok(),
+                None => None,
+            };
+            // the bucket may have emptied while waiting for the slot
+            if self.reserve(&group, Utc::now()).is_none() {
+                return RateLimitPermit {
+                    group,
+                    _in_flight: in_flight,
+                };
+            }
+        }
+    }
+
+    /// How long to wait for the window of `group` to reset, without taking a
+    /// token.
+    fn wait_time(&self, group: &str, now: DateTime<Utc>) -> Option<Duration> {
+        let mut buckets = self.buckets.lock().expect("rate limiter poisoned");
+        Self::bucket_wait(buckets.entry(group.to_string()).or_default(), now)
+    }
+
+    /// Take a token, or return how long to wait for the window to reset.
+    fn reserve(&self, group: &str, now: DateTime<Utc>) -> Option<Duration> {
+        let mut buckets = self.buckets.lock().expect("rate limiter poisoned");
+        let bucket = buckets.entry(group.to_string()).or_default();
+        let wait = Self::bucket_wait(bucket, now);
+        if wait.is_none() {
+            bucket.remaining = bucket
+                .remaining
+                .map(|remaining| remaining.saturating_sub(1));
+        }
+        wait
+    }
+
+    fn bucket_wait(bucket: &mut Bucket, now: DateTime<Utc>) -> Option<Duration> {
+        if let Some(reset) = bucket.reset {
+            if reset <= now {
+                // refill the new window; without a known limit let one
+                // request through to read the headers again
+                *bucket = Bucket {
+                    limit: bucket.limit,
+                    remaining: Some(bucket.limit.unwrap_or(1)),
+                    reset: None,
+                };
+            }
+        }
+        match (bucket.remaining, bucket.reset) {
+            (Some(0), Some(reset)) => Some(
+                (reset - now)
+                    .to_std()
+                    .unwrap_or(Duration::ZERO)
+                    .max(Duration::from_millis(10)),
+            ),
+            // empty without a reset time, e.g. a bare `429`: back off and
+            // let the next request find out
+            (Some(0), None) => {
+                bucket.reset = chrono::Duration::from_std(EMPTY_BUCKET_BACKOFF)
+                    .ok()
+                    .map(|backoff| now + backoff);
+                Some(EMPTY_BUCKET_BACKOFF)
+            }
+            _ => None,
+        }
+    }
+
+    /// Update the bucket of a finished request from its response headers.
+    pub fn observe(&self, permit: &RateLimitPermit, res: &Response) {
+        let rate_limit = RateLimit::from_headers(res.headers());
+        let mut buckets = self.buckets.lock().expect("rate limiter poisoned");
+        let limited = res.status().as_u16() == 429;
+        if !limited && !rate_limit.is_present() {
+            // the server does not limit this group
+            buckets.remove(&permit.group);
+            return;
+        }
+        let mut bucket = Bucket {
+            limit: rate_limit
+                .limit
+                .or_else(|| buckets.get(&permit.group).and_then(|bucket| bucket.limit)),
+            remaining: rate_limit.remaining,
+            reset: rate_limit.reset,
+        };
+        if limited {
+            bucket.remaining = Some(0);
+            if bucket.reset.is_none() {
+                bucket.reset = retry_after(res.headers())
+                    .and_then(|wait| chrono::Duration::from_std(wait).ok())
+                    .map(|wait| Utc::now() + wait);
+            }
+        }
+        if bucket.remaining.is_none() {
+            return;
+        }
+        buckets.insert(permit.group.clone(), bucket);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reserve() {
+        let limiter = RateLimiter::new(RateLimiterConfig::default());
+        let now = Utc::now();
+        let group = group_by_service(HttpMethod::POST, "/users");
+        assert_eq!(group, "POST /users");
+        assert_eq!(limiter.reserve(&group, now), None);
+
+        limiter.buckets.lock().unwrap().insert(
+            group.clone(),
+            Bucket {
+                limit: Some(2),
+                remaining: Some(1),
+                reset: Some(now + chrono::Duration::seconds(30)),
+            },
+        );
+        assert_eq!(limiter.reserve(&group, now), None);
+        let wait = limiter.reserve(&group, now).expect("bucket is empty");
+        assert!(wait > Duration::from_secs(29));
+
+        let later = now + chrono::Duration::seconds(31);
+        assert_eq!(limiter.reserve(&group, later), None);
+        assert_eq!(limiter.reserve(&group, later), None);
+        assert!(limiter.reserve(&group, later).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_max_in_flight() {
+        let limiter = RateLimiter::new(RateLimiterConfig {
+            max_in_flight: Some(1),
+            ..Default::default()
+        });
+        let first = limiter.acquire(HttpMethod::GET, "/users").await;
+        let second = tokio::time::timeout(
+            Duration::from_millis(20),
+            limiter.acquire(HttpMethod::GET, "/users"),
+        )
+        .await;
+        assert!(second.is_err());
+        drop(first);
+        let _third = limiter.acquire(HttpMethod::GET, "/users").await;
+    }
+
+    #[test]
+    fn test_empty_bucket_without_reset() -> Result<(), crate::error::Error> {
+        let limiter = RateLimiter::new(RateLimiterConfig::default());
+        let group = group_by_service(HttpMethod::GET, "/users");
+        let permit = RateLimitPermit {
+            group: group.clone(),
+            _in_flight: None,
+        };
+
+        let res = crate::transport::MockResponse::bytes(429, "").into_response()?;
+        limiter.observe(&permit, &res);
+        let now = Utc::now();
+        assert_eq!(limiter.reserve(&group, now), Some(EMPTY_BUCKET_BACKOFF));
+        assert!(limiter.reserve(&group, now).is_some());
+        let later = now + chrono::Duration::seconds(2);
+        assert_eq!(limiter.reserve(&group, later), None);
+        assert!(limiter.reserve(&group, later).is_some());
+
+        let res = crate::transport::MockResponse::bytes(200, "")
+            .with_header("x-ratelimit-remaining", "0")?
+            .into_response()?;
+        limiter.observe(&permit, &res);
+        assert_eq!(limiter.reserve(&group, now), Some(EMPTY_BUCKET_BACKOFF));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_throttled_group_does_not_hold_slot() {
+        let limiter = RateLimiter::new(RateLimiterConfig {
+            max_in_flight: Some(1),
+            ..Default::default()
+        });
+        limiter.buckets.lock().unwrap().insert(
+            group_by_service(HttpMethod::GET, "/users"),
+            Bucket {
+                remaining: Some(0),
+                reset: Some(Utc::now() + chrono::Duration::seconds(30)),
+                ..Default::default()
+            },
+        );
+        let throttled = limiter.acquire(HttpMethod::GET, "/users");
+        futures_util::pin_mut!(throttled);
+        let waited = tokio::time::timeout(Duration::from_millis(10), &mut throttled).await;
+        assert!(waited.is_err());
+
+        let other = tokio::time::timeout(
+            Duration::from_millis(20),
+            limiter.acquire(HttpMethod::GET, "/teams"),
+        )
+        .await;
+        assert!(other.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_waiters_are_released_up_to_the_limit() {
+        let limiter = Arc::new(RateLimiter::new(RateLimiterConfig::default()));
+        let group = group_by_service(HttpMethod::GET, "/users");
+        limiter.buckets.lock().unwrap().insert(
+            group,
+            Bucket {
+                limit: Some(2),
+                remaining: Some(0),
+                reset: Some(Utc::now() + chrono::Duration::milliseconds(50)),
+            },
+        );
+
+        let released = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        for _ in 0..5 {
+            let (limiter, released) = (limiter.clone(), released.clone());
+            tokio::spawn(async move {
+                let _permit = limiter.acquire(HttpMethod::GET, "/users").await;
+                released.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            });
+        }
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        assert_eq!(released.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+}
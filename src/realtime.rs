@@ -2,7 +2,7 @@ use crate::{client::Client, error::Error};
 use async_fn_stream::try_fn_stream;
 use futures_util::{Stream, StreamExt};
 use serde_json::Value;
-use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
+use tokio_tungstenite::tungstenite::protocol::Message;
 
 pub struct RealTime;
 
@@ -13,7 +13,7 @@ impl RealTime {
         // project_id: &'a str,
     ) -> impl Stream<Item = Result<Value, Error>> + 'a {
         try_fn_stream(|emitter| async move {
-            if channels.len() < 1 {
+            if channels.is_empty() {
                 return Ok(());
             }
             let mut url_search_params = String::new();
@@ -39,7 +39,7 @@ impl RealTime {
                 url_search_params
             );
             println!("{}", url);
-            let (ws_stream, _response) = connect_async(url).await.expect("Can't connect");
+            let ws_stream = client.connect_websocket(&url).await?;
 
             let (mut _write, mut read) = ws_stream.split();
 
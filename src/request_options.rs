@@ -0,0 +1,208 @@
+//! # Request options
+//!
+//! Headers overridden for a single call or a scope, applied to a derived
+//! [`Client`](crate::client::Client) which shares the transport of the one it
+//! was made from. A server acting for many end users keeps one client and
+//! derives a view per request.
+//! ```no_run
+//! # use unofficial_appwrite::{client::Client, error::Error, services::server::account::Account};
+//! use unofficial_appwrite::request_options::RequestOptions;
+//! # async fn run(client: &Client, secret: &str, user_agent: &str) -> Result<(), Error> {
+//! let user = client
+//!     .with_session(secret)?
+//!     .with_options(RequestOptions::new().set_forwarded_user_agent(user_agent)?);
+//! let account = Account::get(&user).await?;
+//! # Ok(())
+//! # }
+//! ```
+use std::str::FromStr;
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+
+use crate::error::Error;
+
+pub const SESSION_HEADER: &str = "x-appwrite-session";
+pub const JWT_HEADER: &str = "x-appwrite-jwt";
+pub const KEY_HEADER: &str = "x-appwrite-key";
+pub const LOCALE_HEADER: &str = "x-appwrite-locale";
+pub const FORWARDED_USER_AGENT_HEADER: &str = "x-forwarded-user-agent";
+pub const FORWARDED_FOR_HEADER: &str = "x-forwarded-for";
+pub const IMPERSONATE_USER_ID_HEADER: &str = "x-appwrite-impersonate-user-id";
+pub const IMPERSONATE_USER_EMAIL_HEADER: &str = "x-appwrite-impersonate-user-email";
+pub const IMPERSONATE_USER_PHONE_HEADER: &str = "x-appwrite-impersonate-user-phone";
+
+const IMPERSONATE_HEADERS: [&str; 3] = [
+    IMPERSONATE_USER_ID_HEADER,
+    IMPERSONATE_USER_EMAIL_HEADER,
+    IMPERSONATE_USER_PHONE_HEADER,
+];
+
+/// Headers to set on a derived client, see
+/// [`Client::with_options`](crate::client::Client::with_options).
+///
+/// Setting a session or a JWT drops the api key and the other credential of the
+/// derived client, so the calls run as the end user and not as the server.
+/// Impersonation keeps the credentials, which must be allowed to impersonate,
+/// and replaces any other impersonation header.
+#[derive(Debug, Clone, Default)]
+pub struct RequestOptions {
+    headers: HeaderMap,
+    remove: Vec<&'static str>,
+}
+
+impl RequestOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_session(&mut self, secret: &str) -> Result<&mut Self, Error> {
+        self.remove.extend([KEY_HEADER, JWT_HEADER]);
+        self.headers
+            .insert(SESSION_HEADER, HeaderValue::from_str(secret)?);
+        Ok(self)
+    }
+
+    pub fn set_jwt(&mut self, jwt: &str) -> Result<&mut Self, Error> {
+        self.remove.extend([KEY_HEADER, SESSION_HEADER]);
+        self.headers.insert(JWT_HEADER, HeaderValue::from_str(jwt)?);
+        Ok(self)
+    }
+
+    pub fn set_locale(&mut self, locale: &str) -> Result<&mut Self, Error> {
+        self.headers
+            .insert(LOCALE_HEADER, HeaderValue::from_str(locale)?);
+        Ok(self)
+    }
+
+    /// User agent of the end user the request is made for.
+    pub fn set_forwarded_user_agent(&mut self, user_agent: &str) -> Result<&mut Self, Error> {
+        self.headers.insert(
+            FORWARDED_USER_AGENT_HEADER,
+            HeaderValue::from_str(user_agent)?,
+        );
+        Ok(self)
+    }
+
+    /// IP address of the end user the request is made for.
+    pub fn set_forwarded_for(&mut self, ip: &str) -> Result<&mut Self, Error> {
+        self.headers
+            .insert(FORWARDED_FOR_HEADER, HeaderValue::from_str(ip)?);
+        Ok(self)
+    }
+
+    /// Act as the user with this ID.
+    pub fn set_impersonate_user_id(&mut self, user_id: &str) -> Result<&mut Self, Error> {
+        self.impersonate(IMPERSONATE_USER_ID_HEADER, user_id)
+    }
+
+    /// Act as the user with this email.
+    pub fn set_impersonate_user_email(&mut self, email: &str) -> Result<&mut Self, Error> {
+        self.impersonate(IMPERSONATE_USER_EMAIL_HEADER, email)
+    }
+
+    /// Act as the user with this phone number.
+    pub fn set_impersonate_user_phone(&mut self, phone: &str) -> Result<&mut Self, Error> {
+        self.impersonate(IMPERSONATE_USER_PHONE_HEADER, phone)
+    }
+
+    fn impersonate(&mut self, header: &'static str, value: &str) -> Result<&mut Self, Error> {
+        let value = HeaderValue::from_str(value)?;
+        for other in IMPERSONATE_HEADERS {
+            self.headers.remove(other);
+        }
+        self.remove.extend(IMPERSONATE_HEADERS);
+        self.headers.insert(header, value);
+        Ok(self)
+    }
+
+    pub fn add_header(&mut self, key: &str, value: &str) -> Result<&mut Self, Error> {
+        self.headers
+            .insert(HeaderName::from_str(key)?, HeaderValue::from_str(value)?);
+        Ok(self)
+    }
+
+    /// Apply the options on top of client headers.
+    pub(crate) fn apply(&self, headers: &mut HeaderMap) {
+        for key in &self.remove {
+            if !self.headers.contains_key(*key) {
+                headers.remove(*key);
+            }
+        }
+        headers.extend(self.headers.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        client::ClientBuilder,
+        enumm::HttpMethod,
+        services::server::users::Users,
+        transport::{MockResponse, MockTransport},
+    };
+
+    #[tokio::test]
+    async fn test_with_session() -> Result<(), Error> {
+        let mock = Arc::new(MockTransport::new());
+        mock.on(
+            HttpMethod::GET,
+            "/users/user/prefs",
+            MockResponse::json(200, json!({})),
+        );
+        let client = ClientBuilder::default()
+            .set_project("project")?
+            .set_key("secret-key")?
+            .set_transport(mock.clone())?
+            .build()?;
+
+        let user = client.with_session("session-secret")?.with_options(
+            RequestOptions::new()
+                .set_forwarded_user_agent("Mozilla/5.0")?
+                .set_forwarded_for("203.0.113.7")?
+                .set_locale("de")?,
+        );
+        Users::get_prefs(&user, "user").await?;
+
+        let request = mock.last_request().expect("request recorded");
+        assert_eq!(request.header(SESSION_HEADER), Some("session-secret"));
+        assert_eq!(request.header(KEY_HEADER), None);
+        assert_eq!(
+            request.header(FORWARDED_USER_AGENT_HEADER),
+            Some("Mozilla/5.0")
+        );
+        assert_eq!(request.header(FORWARDED_FOR_HEADER), Some("203.0.113.7"));
+        assert_eq!(request.header(LOCALE_HEADER), Some("de"));
+        assert_eq!(request.header("x-appwrite-project"), Some("project"));
+
+        Users::get_prefs(&client, "user").await?;
+        let request = mock.last_request().expect("request recorded");
+        assert_eq!(request.header(KEY_HEADER), Some("secret-key"));
+        assert_eq!(request.header(SESSION_HEADER), None);
+
+        let by_email = client.with_impersonate_user_email("ada@example.com")?;
+        Users::get_prefs(&by_email.with_impersonate_user_id("user")?, "user").await?;
+        let request = mock.last_request().expect("request recorded");
+        assert_eq!(request.header(IMPERSONATE_USER_ID_HEADER), Some("user"));
+        assert_eq!(request.header(IMPERSONATE_USER_EMAIL_HEADER), None);
+        assert_eq!(request.header(KEY_HEADER), Some("secret-key"));
+
+        Users::get_prefs(&client.with_impersonate_user_phone("+15550100")?, "user").await?;
+        let request = mock.last_request().expect("request recorded");
+        assert_eq!(
+            request.header(IMPERSONATE_USER_PHONE_HEADER),
+            Some("+15550100")
+        );
+        Users::get_prefs(&by_email, "user").await?;
+        let request = mock.last_request().expect("request recorded");
+        assert_eq!(
+            request.header(IMPERSONATE_USER_EMAIL_HEADER),
+            Some("ada@example.com")
+        );
+        Ok(())
+    }
+}
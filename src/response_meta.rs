@@ -0,0 +1,191 @@
+//! # Response metadata
+//!
+//! Status, headers and rate-limit information of a response, returned next to
+//! the deserialized model by [`Client::with_meta`](crate::client::Client::with_meta),
+//! or next to the error in a [`MetaError`] when the call failed.
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use futures_util::future::BoxFuture;
+use reqwest::{header::HeaderMap, Response};
+
+use crate::{
+    error::Error,
+    interceptor::{Interceptor, RequestParts},
+};
+
+/// Appwrite rate-limit headers of a response.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RateLimit {
+    /// `X-RateLimit-Limit`: requests allowed in the current window.
+    pub limit: Option<u64>,
+    /// `X-RateLimit-Remaining`: requests left in the current window.
+    pub remaining: Option<u64>,
+    /// `X-RateLimit-Reset`: when the current window resets.
+    pub reset: Option<DateTime<Utc>>,
+}
+
+impl RateLimit {
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let number = |key: &str| {
+            headers
+                .get(key)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse::<u64>().ok())
+        };
+        Self {
+            limit: number("x-ratelimit-limit"),
+            remaining: number("x-ratelimit-remaining"),
+            reset: number("x-ratelimit-reset")
+                .and_then(|secs| i64::try_from(secs).ok())
+                .and_then(|secs| DateTime::from_timestamp(secs, 0)),
+        }
+    }
+
+    /// Whether the response carried any rate-limit header.
+    pub fn is_present(&self) -> bool {
+        self.limit.is_some() || self.remaining.is_some() || self.reset.is_some()
+    }
+}
+
+/// Metadata of a response.
+#[derive(Debug, Clone, Default)]
+pub struct ResponseMeta {
+    pub status: u16,
+    pub headers: HeaderMap,
+    pub rate_limit: RateLimit,
+}
+
+impl ResponseMeta {
+    pub fn from_response(res: &Response) -> Self {
+        Self {
+            status: res.status().as_u16(),
+            headers: res.headers().clone(),
+            rate_limit: RateLimit::from_headers(res.headers()),
+        }
+    }
+}
+
+/// A model together with the metadata of the response it was read from.
+#[derive(Debug, Clone)]
+pub struct WithMeta<T> {
+    pub data: T,
+    pub meta: ResponseMeta,
+}
+
+impl<T> WithMeta<T> {
+    pub fn into_inner(self) -> T {
+        self.data
+    }
+}
+
+/// Failed [`Client::with_meta`](crate::client::Client::with_meta) call, with
+/// the metadata of the last response, e.g. the `Retry-After` and rate-limit
+/// headers of a 429. `meta` is `None` when no response was received.
+#[derive(Debug)]
+pub struct MetaError {
+    pub error: Error,
+    pub meta: Option<ResponseMeta>,
+}
+
+impl std::fmt::Display for MetaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl std::error::Error for MetaError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl From<MetaError> for Error {
+    fn from(err: MetaError) -> Self {
+        err.error
+    }
+}
+
+/// Interceptor recording the metadata of the last response it sees.
+#[derive(Debug, Default)]
+pub(crate) struct MetaCapture {
+    pub(crate) last: Arc<Mutex<Option<ResponseMeta>>>,
+}
+
+impl Interceptor for MetaCapture {
+    fn on_response<'a>(
+        &'a self,
+        _request: &'a RequestParts,
+        response: Response,
+    ) -> BoxFuture<'a, Result<Response, Error>> {
+        Box::pin(async move {
+            *self.last.lock().expect("meta capture poisoned") =
+                Some(ResponseMeta::from_response(&response));
+            Ok(response)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        enumm::HttpMethod,
+        services::server::users::Users,
+        transport::{MockResponse, MockTransport},
+    };
+
+    #[tokio::test]
+    async fn test_with_meta() -> Result<(), Error> {
+        let (mock, client) = MockTransport::client()?;
+        mock.on(
+            HttpMethod::GET,
+            "/users/user/prefs",
+            MockResponse::json(200, json!({"theme": "dark"}))
+                .with_header("x-ratelimit-limit", "60")?
+                .with_header("x-ratelimit-remaining", "59")?
+                .with_header("x-ratelimit-reset", "1700000000")?,
+        );
+
+        let prefs = client
+            .with_meta(|client| async move { Users::get_prefs(&client, "user").await })
+            .await?;
+        assert_eq!(prefs.meta.status, 200);
+        assert_eq!(prefs.meta.rate_limit.limit, Some(60));
+        assert_eq!(prefs.meta.rate_limit.remaining, Some(59));
+        assert_eq!(
+            prefs.meta.rate_limit.reset,
+            DateTime::from_timestamp(1_700_000_000, 0)
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_with_meta_keeps_meta_on_error() -> Result<(), Error> {
+        let (mock, client) = MockTransport::client()?;
+        mock.on(
+            HttpMethod::GET,
+            "/users/user/prefs",
+            MockResponse::json(
+                429,
+                json!({"message": "Too many requests", "code": 429,
+                       "type": "general_rate_limit_exceeded"}),
+            )
+            .with_header("x-ratelimit-remaining", "0")?
+            .with_header("retry-after", "30")?,
+        );
+
+        let err = client
+            .with_meta(|client| async move { Users::get_prefs(&client, "user").await })
+            .await
+            .unwrap_err();
+        assert!(err.error.is_rate_limited());
+        let meta = err.meta.unwrap();
+        assert_eq!(meta.status, 429);
+        assert_eq!(meta.rate_limit.remaining, Some(0));
+        assert_eq!(meta.headers["retry-after"], "30");
+        Ok(())
+    }
+}
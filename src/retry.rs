@@ -0,0 +1,190 @@
+//! # Retry
+//!
+//! Retry policy used by [`Client::call`](crate::client::Client::call) to resend
+//! requests that failed with a transient error.
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+
+use crate::{enumm::HttpMethod, error::Error};
+
+/// Retry with exponential backoff and full jitter.
+///
+/// The delay before attempt `n + 1` is a random value between zero and
+/// `min(max_delay, base_delay * 2^(n - 1))`. When the server answers with a
+/// `Retry-After` header that delay is used instead, and the request is not
+/// retried if it asks to wait longer than `max_delay`.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one.
+    pub max_attempts: u32,
+    /// Delay used for the first retry.
+    pub base_delay: Duration,
+    /// Upper bound for a single delay.
+    pub max_delay: Duration,
+    /// HTTP status codes which are retried.
+    pub status_codes: Vec<u16>,
+    /// Retry when the connection could not be established.
+    pub retry_connect_errors: bool,
+    /// Retry when the request timed out.
+    pub retry_timeouts: bool,
+    /// Retry when the connection was reset or closed while sending the request.
+    pub retry_request_errors: bool,
+    /// Also retry `POST` and `PATCH` requests, which are not idempotent.
+    pub retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(10),
+            status_codes: vec![429, 502, 503, 504],
+            retry_connect_errors: true,
+            retry_timeouts: true,
+            retry_request_errors: true,
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Whether a request with this method may be resent at all.
+    pub fn allows_method(&self, method: HttpMethod) -> bool {
+        self.retry_non_idempotent || method.is_idempotent()
+    }
+
+    /// Whether the response status should be retried.
+    pub fn is_retryable_status(&self, status: u16) -> bool {
+        self.status_codes.contains(&status)
+    }
+
+    /// Whether a transport error should be retried.
+    pub fn is_retryable_network(&self, err: &reqwest::Error) -> bool {
+        (self.retry_connect_errors && err.is_connect())
+            || (self.retry_timeouts && err.is_timeout())
+            || (self.retry_request_errors && err.is_request())
+    }
+
+    /// Whether an [`Error`] returned by the client should be retried, e.g. a
+    /// `503` from Appwrite or an HTML `502` page from a proxy.
+    pub fn is_retryable(&self, err: &Error) -> bool {
+        if let Error::Network(err) = err {
+            if self.is_retryable_network(err) {
+                return true;
+            }
+        }
+        err.status()
+            .is_some_and(|status| self.is_retryable_status(status))
+    }
+
+    /// Backoff delay to wait after the given (1-based) failed attempt.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exp = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)));
+        let cap = exp.min(self.max_delay);
+        if cap.is_zero() {
+            return cap;
+        }
+        let millis = u64::try_from(cap.as_millis()).unwrap_or(u64::MAX);
+        Duration::from_millis(rand::thread_rng().gen_range(0..=millis))
+    }
+
+    /// Delay to wait after a retryable response, honouring `Retry-After`.
+    ///
+    /// Returns `None` when the server asks to wait longer than `max_delay`.
+    pub fn delay_for_response(&self, attempt: u32, headers: &HeaderMap) -> Option<Duration> {
+        match retry_after(headers) {
+            Some(delay) if delay > self.max_delay => None,
+            Some(delay) => Some(delay),
+            None => Some(self.backoff(attempt)),
+        }
+    }
+}
+
+/// Parse a `Retry-After` header given either in seconds or as an HTTP date.
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&Utc) - Utc::now())
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_backoff_is_bounded() {
+        let policy = RetryPolicy {
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(250),
+            ..Default::default()
+        };
+        for attempt in 1..10 {
+            assert!(policy.backoff(attempt) <= Duration::from_millis(250));
+        }
+        assert!(policy.backoff(1) <= Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let policy = RetryPolicy::default();
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("2"));
+        assert_eq!(
+            policy.delay_for_response(1, &headers),
+            Some(Duration::from_secs(2))
+        );
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("3600"));
+        assert_eq!(policy.delay_for_response(1, &headers), None);
+
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(retry_after(&headers), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_retryable() {
+        let policy = RetryPolicy::default();
+        assert!(policy.allows_method(HttpMethod::GET));
+        assert!(!policy.allows_method(HttpMethod::POST));
+        assert!(policy.is_retryable(&Error::AppWriteError {
+            message: "busy".into(),
+            code: Some(503),
+            response: None,
+            error_type: None,
+            status: 503,
+            version: None,
+        }));
+        assert!(policy.is_retryable(&Error::UnexpectedResponse {
+            status: 502,
+            content_type: Some("text/html".into()),
+            method: HttpMethod::GET,
+            path: "/users".into(),
+            body: "<html>Bad Gateway</html>".into(),
+        }));
+        assert!(!policy.is_retryable(&Error::AppWriteError {
+            message: "missing".into(),
+            code: Some(404),
+            response: None,
+            error_type: None,
+            status: 404,
+            version: None,
+        }));
+    }
+}
@@ -0,0 +1,479 @@
+//! # Schema
+//!
+//! Collection schemas described in Rust: attributes and indexes, created with
+//! the `create_*_attribute` and `create_index` calls of
+//! [`Databases`](crate::services::server::databases::Databases).
+//!
+//! With the `derive` feature a struct becomes the single source of truth for
+//! its collection:
+//! ```ignore
+//! use serde::{Deserialize, Serialize};
+//! use unofficial_appwrite::schema::AppwriteCollection;
+//!
+//! #[derive(Serialize, Deserialize, AppwriteCollection)]
+//! #[appwrite(collection = "books", name = "Books")]
+//! #[appwrite(index(key = "title_search", kind = "fulltext", attributes("title")))]
+//! struct Book {
+//!     #[appwrite(size = 255)]
+//!     title: String,
+//!     #[appwrite(email)]
+//!     contact: Option<String>,
+//!     #[appwrite(min = 0, max = 5, default = 3)]
+//!     rating: Option<i64>,
+//!     #[appwrite(elements("draft", "published"), indexed)]
+//!     status: String,
+//!     #[appwrite(relationship(related = "authors", kind = "manyToOne", on_delete = "setNull"))]
+//!     author: Option<serde_json::Value>,
+//! }
+//! ```
+use std::collections::HashMap;
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    client::Client,
+    enums::{
+        index_type::IndexType, relation_mutate::RelationMutate, relationship_type::RelationshipType,
+    },
+    error::Error,
+    models::{attribute::Attribute, collection::Collection},
+    services::server::databases::Databases,
+    wait::WaitOptions,
+};
+
+#[cfg(feature = "derive")]
+pub use unofficial_appwrite_derive::AppwriteCollection;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use serde_json;
+}
+
+/// Type and type specific settings of an attribute.
+///
+/// In a schema file the type is the `type` field of the attribute, e.g.
+/// `{"key": "title", "type": "string", "size": 255}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(
+    tag = "type",
+    rename_all = "lowercase",
+    rename_all_fields = "camelCase"
+)]
+pub enum AttributeKind {
+    String {
+        size: u64,
+        #[serde(default)]
+        encrypt: bool,
+    },
+    Integer {
+        #[serde(default)]
+        min: Option<i64>,
+        #[serde(default)]
+        max: Option<i64>,
+    },
+    Float {
+        #[serde(default)]
+        min: Option<f64>,
+        #[serde(default)]
+        max: Option<f64>,
+    },
+    Boolean,
+    Datetime,
+    Email,
+    Url,
+    Ip,
+    Enum {
+        elements: Vec<String>,
+    },
+    Relationship {
+        related_collection: String,
+        relation_type: RelationshipType,
+        #[serde(default)]
+        two_way: bool,
+        #[serde(default)]
+        two_way_key: Option<String>,
+        #[serde(default)]
+        on_delete: RelationMutate,
+    },
+}
+
+/// Attribute of a collection.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttributeSchema {
+    pub key: String,
+    #[serde(flatten)]
+    pub kind: AttributeKind,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub default: Option<Value>,
+    #[serde(default)]
+    pub array: bool,
+}
+
+impl AttributeSchema {
+    /// Schema of an attribute returned by `list_attributes` or
+    /// `get_attribute`, `None` for an unknown type.
+    pub fn from_attribute(attribute: &Attribute) -> Option<Self> {
+        let schema =
+            |key: &str, kind, required, default: Option<Value>, array: Option<bool>| Self {
+                key: key.to_string(),
+                kind,
+                required,
+                default: default.filter(|default| !default.is_null()),
+                array: array.unwrap_or(false),
+            };
+        Some(match attribute {
+            Attribute::String(a) => schema(
+                &a.key,
+                AttributeKind::String {
+                    size: a.size,
+                    encrypt: a.encrypt.unwrap_or(false),
+                },
+                a.xrequired,
+                a.xdefault.as_ref().map(|v| json!(v)),
+                a.array,
+            ),
+            Attribute::Integer(a) => schema(
+                &a.key,
+                AttributeKind::Integer {
+                    min: a.min,
+                    max: a.max,
+                },
+                a.xrequired,
+                a.xdefault.map(|v| json!(v)),
+                a.array,
+            ),
+            Attribute::Float(a) => schema(
+                &a.key,
+                AttributeKind::Float {
+                    min: a.min,
+                    max: a.max,
+                },
+                a.xrequired,
+                a.xdefault.map(|v| json!(v)),
+                a.array,
+            ),
+            Attribute::Boolean(a) => schema(
+                &a.key,
+                AttributeKind::Boolean,
+                a.xrequired.unwrap_or(false),
+                a.xdefault.map(|v| json!(v)),
+                a.array,
+            ),
+            Attribute::Datetime(a) => schema(
+                &a.key,
+                AttributeKind::Datetime,
+                a.xrequired,
+                a.xdefault.as_ref().map(|v| json!(v)),
+                a.array,
+            ),
+            Attribute::Email(a) => schema(
+                &a.key,
+                AttributeKind::Email,
+                a.xrequired,
+                a.xdefault.as_ref().map(|v| json!(v)),
+                a.array,
+            ),
+            Attribute::Url(a) => schema(
+                &a.key,
+                AttributeKind::Url,
+                a.xrequired,
+                a.xdefault.as_ref().map(|v| json!(v)),
+                a.array,
+            ),
+            Attribute::Ip(a) => schema(
+                &a.key,
+                AttributeKind::Ip,
+                a.xrequired,
+                a.xdefault.as_ref().map(|v| json!(v)),
+                a.array,
+            ),
+            Attribute::Enum(a) => schema(
+                &a.key,
+                AttributeKind::Enum {
+                    elements: a
+                        .elements
+                        .iter()
+                        .filter_map(|v| v.as_str().map(str::to_string))
+                        .collect(),
+                },
+                a.xrequired,
+                a.xdefault.as_ref().map(|v| json!(v)),
+                a.array,
+            ),
+            Attribute::Relationship(a) => schema(
+                &a.key,
+                AttributeKind::Relationship {
+                    related_collection: a.related_collection.clone(),
+                    relation_type: serde_json::from_value(json!(a.relation_type)).ok()?,
+                    two_way: a.two_way,
+                    two_way_key: Some(a.two_way_key.clone()).filter(|key| !key.is_empty()),
+                    on_delete: serde_json::from_value(json!(a.on_delete)).ok()?,
+                },
+                a.xrequired,
+                None,
+                a.array,
+            ),
+            Attribute::Unknown(_) => return None,
+        })
+    }
+
+    /// Args of the matching `create_*_attribute` call.
+    pub fn to_args(&self) -> HashMap<String, Value> {
+        let mut args = HashMap::new();
+        args.insert("key".to_string(), json!(self.key));
+        if let AttributeKind::Relationship {
+            related_collection,
+            relation_type,
+            two_way,
+            two_way_key,
+            on_delete,
+        } = &self.kind
+        {
+            args.insert("relatedCollectionId".to_string(), json!(related_collection));
+            args.insert("type".to_string(), json!(relation_type.as_serialized()));
+            args.insert("twoWay".to_string(), json!(two_way));
+            if let Some(two_way_key) = two_way_key {
+                args.insert("twoWayKey".to_string(), json!(two_way_key));
+            }
+            args.insert("onDelete".to_string(), json!(on_delete.as_serialized()));
+            return args;
+        }
+
+        args.insert("required".to_string(), json!(self.required));
+        args.insert("array".to_string(), json!(self.array));
+        if let Some(default) = &self.default {
+            args.insert("default".to_string(), default.clone());
+        }
+        match &self.kind {
+            AttributeKind::String { size, encrypt } => {
+                args.insert("size".to_string(), json!(size));
+                if *encrypt {
+                    args.insert("encrypt".to_string(), json!(true));
+                }
+            }
+            AttributeKind::Integer { min, max } => {
+                if let Some(min) = min {
+                    args.insert("min".to_string(), json!(min));
+                }
+                if let Some(max) = max {
+                    args.insert("max".to_string(), json!(max));
+                }
+            }
+            AttributeKind::Float { min, max } => {
+                if let Some(min) = min {
+                    args.insert("min".to_string(), json!(min));
+                }
+                if let Some(max) = max {
+                    args.insert("max".to_string(), json!(max));
+                }
+            }
+            AttributeKind::Enum { elements } => {
+                args.insert("elements".to_string(), json!(elements));
+            }
+            _ => {}
+        }
+        args
+    }
+
+    /// Create the attribute on `collection_id`.
+    pub async fn create(
+        &self,
+        client: &Client,
+        database_id: &str,
+        collection_id: &str,
+    ) -> Result<(), Error> {
+        let args = self.to_args();
+        let (db, col) = (database_id, collection_id);
+        match &self.kind {
+            AttributeKind::String { .. } => {
+                Databases::create_string_attribute(client, db, col, args).await?;
+            }
+            AttributeKind::Integer { .. } => {
+                Databases::create_integer_attribute(client, db, col, args).await?;
+            }
+            AttributeKind::Float { .. } => {
+                Databases::create_float_attribute(client, db, col, args).await?;
+            }
+            AttributeKind::Boolean => {
+                Databases::create_boolean_attribute(client, db, col, args).await?;
+            }
+            AttributeKind::Datetime => {
+                Databases::create_date_time_attribute(client, db, col, args).await?;
+            }
+            AttributeKind::Email => {
+                Databases::create_email_attribute(client, db, col, args).await?;
+            }
+            AttributeKind::Url => {
+                Databases::create_url_attribute(client, db, col, args).await?;
+            }
+            AttributeKind::Ip => {
+                Databases::create_ip_attribute(client, db, col, args).await?;
+            }
+            AttributeKind::Enum { .. } => {
+                Databases::create_enum_attribute(client, db, col, args).await?;
+            }
+            AttributeKind::Relationship { .. } => {
+                Databases::create_relationship_attribute(client, db, col, args).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Args of the matching `update_*_attribute` call. A missing default is
+    /// sent as `null` to clear the default on the server.
+    pub fn update_args(&self) -> HashMap<String, Value> {
+        let mut args = self.to_args();
+        if let AttributeKind::Relationship { on_delete, .. } = &self.kind {
+            args.clear();
+            args.insert("onDelete".to_string(), json!(on_delete.as_serialized()));
+            return args;
+        }
+        args.remove("key");
+        args.remove("array");
+        args.remove("encrypt");
+        args.entry("default".to_string()).or_insert(Value::Null);
+        args
+    }
+
+    /// Update the settings of attribute `key` on `collection_id`.
+    pub async fn update(
+        &self,
+        client: &Client,
+        database_id: &str,
+        collection_id: &str,
+    ) -> Result<(), Error> {
+        let args = self.update_args();
+        let (db, col, key) = (database_id, collection_id, self.key.as_str());
+        match &self.kind {
+            AttributeKind::String { .. } => {
+                Databases::update_string_attribute(client, db, col, key, args).await?;
+            }
+            AttributeKind::Integer { .. } => {
+                Databases::update_integer_attribute(client, db, col, key, args).await?;
+            }
+            AttributeKind::Float { .. } => {
+                Databases::update_float_attribute(client, db, col, key, args).await?;
+            }
+            AttributeKind::Boolean => {
+                Databases::update_boolean_attribute(client, db, col, key, args).await?;
+            }
+            AttributeKind::Datetime => {
+                Databases::update_date_time_attribute(client, db, col, key, args).await?;
+            }
+            AttributeKind::Email => {
+                Databases::update_email_attribute(client, db, col, key, args).await?;
+            }
+            AttributeKind::Url => {
+                Databases::update_url_attribute(client, db, col, key, args).await?;
+            }
+            AttributeKind::Ip => {
+                Databases::update_ip_attribute(client, db, col, key, args).await?;
+            }
+            AttributeKind::Enum { .. } => {
+                Databases::update_enum_attribute(client, db, col, key, args).await?;
+            }
+            AttributeKind::Relationship { .. } => {
+                Databases::update_relationship_attribute(client, db, col, key, args).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Index of a collection.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexSchema {
+    pub key: String,
+    #[serde(rename = "type")]
+    pub index_type: IndexType,
+    pub attributes: Vec<String>,
+    /// `ASC` or `DESC` per attribute, empty for the server default.
+    #[serde(default)]
+    pub orders: Vec<String>,
+}
+
+impl IndexSchema {
+    /// Args of the `create_index` call.
+    pub fn to_args(&self) -> HashMap<String, Value> {
+        let mut args = HashMap::new();
+        args.insert("key".to_string(), json!(self.key));
+        args.insert("type".to_string(), json!(self.index_type.as_serialized()));
+        args.insert("attributes".to_string(), json!(self.attributes));
+        if !self.orders.is_empty() {
+            args.insert("orders".to_string(), json!(self.orders));
+        }
+        args
+    }
+
+    pub async fn create(
+        &self,
+        client: &Client,
+        database_id: &str,
+        collection_id: &str,
+    ) -> Result<(), Error> {
+        Databases::create_index(client, database_id, collection_id, self.to_args()).await?;
+        Ok(())
+    }
+}
+
+/// Collection with its attributes and indexes.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionSchema {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub document_security: bool,
+    #[serde(default)]
+    pub attributes: Vec<AttributeSchema>,
+    #[serde(default)]
+    pub indexes: Vec<IndexSchema>,
+}
+
+impl CollectionSchema {
+    /// Create the collection and its attributes and indexes. The indexes are
+    /// created once the attributes are available.
+    ///* permissions => vec(string)?
+    pub async fn create(
+        &self,
+        client: &Client,
+        database_id: &str,
+        mut args: HashMap<String, Value>,
+    ) -> Result<Collection, Error> {
+        args.insert("collectionId".to_string(), json!(self.id));
+        args.insert("name".to_string(), json!(self.name));
+        args.insert(
+            "documentSecurity".to_string(),
+            json!(self.document_security),
+        );
+        let collection = Databases::create_collection(client, database_id, args).await?;
+
+        for attribute in &self.attributes {
+            attribute.create(client, database_id, &self.id).await?;
+        }
+        if !self.indexes.is_empty() {
+            Databases::wait_for_collection_ready(
+                client,
+                database_id,
+                &self.id,
+                &WaitOptions::default(),
+            )
+            .await?;
+        }
+        for index in &self.indexes {
+            index.create(client, database_id, &self.id).await?;
+        }
+        Ok(collection)
+    }
+}
+
+/// Struct stored as the documents of an Appwrite collection, usually derived
+/// with `#[derive(AppwriteCollection)]`.
+pub trait AppwriteCollection: Serialize + DeserializeOwned {
+    const COLLECTION_ID: &'static str;
+
+    fn schema() -> CollectionSchema;
+}
@@ -892,7 +892,7 @@ impl Account {
 
         args.insert(
             "project".to_string(),
-            get_content_header_value(&client, "project").into(),
+            get_content_header_value(client, "project").into(),
         );
 
         let api_headers = app_json_header!();
@@ -1051,7 +1051,8 @@ mod tests {
 
     use super::Account;
 
-    //#[tokio::test]
+    #[tokio::test]
+    #[ignore = "needs a running Appwrite server"]
     async fn test_account() -> Result<(), Error> {
         let client = ClientBuilder::default()
             .set_endpoint("http://127.0.0.1/v1")?
@@ -1072,8 +1073,7 @@ mod tests {
         .await?;
         assert_eq!(user_res.email, "fakeemailacc@email.com");
 
-        let delete_user = Users::delete(&client, &user_res.id).await?;
-        assert_eq!(delete_user, ());
+        Users::delete(&client, &user_res.id).await?;
 
         Ok(())
     }
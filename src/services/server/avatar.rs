@@ -37,11 +37,11 @@ impl Avatars {
         let api_path = "/avatars/browsers/{code}".replace("{code}", code);
         args.insert(
             "project".into(),
-            get_content_header_value(&client, "project").into(),
+            get_content_header_value(client, "project").into(),
         );
         args.insert(
             "key".into(),
-            get_content_header_value(&client, "key").into(),
+            get_content_header_value(client, "key").into(),
         );
 
         let api_headers = app_json_header!();
@@ -76,11 +76,11 @@ impl Avatars {
 
         args.insert(
             "project".into(),
-            get_content_header_value(&client, "project").into(),
+            get_content_header_value(client, "project").into(),
         );
         args.insert(
             "key".into(),
-            get_content_header_value(&client, "key").into(),
+            get_content_header_value(client, "key").into(),
         );
 
         let api_headers = app_json_header!();
@@ -106,11 +106,11 @@ impl Avatars {
 
         args.insert(
             "project".into(),
-            get_content_header_value(&client, "project").into(),
+            get_content_header_value(client, "project").into(),
         );
         args.insert(
             "key".into(),
-            get_content_header_value(&client, "key").into(),
+            get_content_header_value(client, "key").into(),
         );
 
         let api_headers = app_json_header!();
@@ -146,11 +146,11 @@ impl Avatars {
 
         args.insert(
             "project".into(),
-            get_content_header_value(&client, "project").into(),
+            get_content_header_value(client, "project").into(),
         );
         args.insert(
             "key".into(),
-            get_content_header_value(&client, "key").into(),
+            get_content_header_value(client, "key").into(),
         );
 
         let api_headers = app_json_header!();
@@ -185,11 +185,11 @@ impl Avatars {
 
         args.insert(
             "project".into(),
-            get_content_header_value(&client, "project").into(),
+            get_content_header_value(client, "project").into(),
         );
         args.insert(
             "key".into(),
-            get_content_header_value(&client, "key").into(),
+            get_content_header_value(client, "key").into(),
         );
 
         let api_headers = app_json_header!();
@@ -231,11 +231,11 @@ impl Avatars {
 
         args.insert(
             "project".into(),
-            get_content_header_value(&client, "project").into(),
+            get_content_header_value(client, "project").into(),
         );
         args.insert(
             "key".into(),
-            get_content_header_value(&client, "key").into(),
+            get_content_header_value(client, "key").into(),
         );
 
         let api_headers = app_json_header!();
@@ -264,11 +264,11 @@ impl Avatars {
 
         args.insert(
             "project".into(),
-            get_content_header_value(&client, "project").into(),
+            get_content_header_value(client, "project").into(),
         );
         args.insert(
             "key".into(),
-            get_content_header_value(&client, "key").into(),
+            get_content_header_value(client, "key").into(),
         );
 
         let api_headers = app_json_header!();
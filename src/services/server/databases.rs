@@ -9,17 +9,22 @@ use crate::{
     client::Client,
     enumm::HttpMethod,
     error::Error,
+    migration::{DatabaseSchema, MigrationOptions, MigrationPlan},
     models::{
-        attribute_boolean::AttributeBoolean, attribute_datetime::AttributeDateTime,
-        attribute_email::AttributeEmail, attribute_enum::AttributeEnum,
-        attribute_float::AttributeFloat, attribute_integer::AttributeInteger,
-        attribute_ip::AttributeIp, attribute_list::AttributeList,
-        attribute_relationship::AttributeRelationship, attribute_string::AttributeString,
-        attribute_url::AttributeUrl, collection::Collection, collection_list::CollectionList,
-        database::Database, database_list::DatabaseList, document::Document,
-        document_list::DocumentList, index::Index, index_list::IndexList,
+        attribute::Attribute, attribute_boolean::AttributeBoolean,
+        attribute_datetime::AttributeDateTime, attribute_email::AttributeEmail,
+        attribute_enum::AttributeEnum, attribute_float::AttributeFloat,
+        attribute_integer::AttributeInteger, attribute_ip::AttributeIp,
+        attribute_list::AttributeList, attribute_relationship::AttributeRelationship,
+        attribute_string::AttributeString, attribute_url::AttributeUrl, collection::Collection,
+        collection_list::CollectionList, database::Database, database_list::DatabaseList,
+        document::Document, document_list::DocumentList, index::Index, index_list::IndexList,
+        transaction::Transaction, transaction_list::TransactionList, typed_document::TypedDocument,
     },
+    schema::AppwriteCollection,
+    wait::{check_status, poll, Poll, WaitOptions},
 };
+use serde::{de::DeserializeOwned, Serialize};
 use serde_json::Value;
 
 pub struct Databases;
@@ -211,6 +216,47 @@ impl Databases {
         Ok(res.json().await?)
     }
 
+    /// Wait for collection ready
+    ///
+    /// Poll [`get_collection`](Self::get_collection) until every attribute
+    /// and index of the collection is `available`. Fails when one of them is
+    /// `failed` or `stuck`, or after `options.timeout`.
+    pub async fn wait_for_collection_ready(
+        client: &Client,
+        database_id: &str,
+        collection_id: &str,
+        options: &WaitOptions,
+    ) -> Result<Collection, Error> {
+        let resource = format!("collection `{}`", collection_id);
+        poll(&resource, options, || async {
+            let collection = Self::get_collection(client, database_id, collection_id).await?;
+            let statuses = collection
+                .attributes
+                .iter()
+                .map(|attribute| {
+                    (
+                        format!("attribute `{}.{}`", collection_id, attribute.key()),
+                        attribute.status(),
+                        attribute.error(),
+                    )
+                })
+                .chain(collection.indexes.iter().map(|index| {
+                    (
+                        format!("index `{}.{}`", collection_id, index.key),
+                        index.status.as_str(),
+                        index.error.as_str(),
+                    )
+                }));
+            for (resource, status, error) in statuses {
+                if let Poll::Pending(status) = check_status(&resource, status, error)? {
+                    return Ok(Poll::Pending(format!("{} {}", resource, status)));
+                }
+            }
+            Ok(Poll::Ready(collection))
+        })
+        .await
+    }
+
     /// Update collection
     ///
     /// Update a collection by its unique ID.
@@ -971,7 +1017,7 @@ impl Databases {
         database_id: &str,
         collection_id: &str,
         key: &str,
-    ) -> Result<Value, Error> {
+    ) -> Result<Attribute, Error> {
         //const API_PATH: &str = "/databases";
         let api_path = "/databases/{databaseId}/collections/{collectionId}/attributes/{key}"
             .replace("{databaseId}", database_id)
@@ -986,7 +1032,32 @@ impl Databases {
             .call(HttpMethod::GET, api_path.as_str(), api_headers, &args, None)
             .await?;
 
-        Ok(res.json::<Value>().await?)
+        Ok(res.json().await?)
+    }
+
+    /// Wait for attribute
+    ///
+    /// Poll [`get_attribute`](Self::get_attribute) until the attribute is
+    /// `available`. Fails when it is `failed` or `stuck`, or after
+    /// `options.timeout`.
+    pub async fn wait_for_attribute(
+        client: &Client,
+        database_id: &str,
+        collection_id: &str,
+        key: &str,
+        options: &WaitOptions,
+    ) -> Result<Attribute, Error> {
+        let resource = format!("attribute `{}.{}`", collection_id, key);
+        poll(&resource, options, || async {
+            let attribute = Self::get_attribute(client, database_id, collection_id, key).await?;
+            Ok(
+                match check_status(&resource, attribute.status(), attribute.error())? {
+                    Poll::Ready(()) => Poll::Ready(attribute),
+                    Poll::Pending(status) => Poll::Pending(status),
+                },
+            )
+        })
+        .await
     }
 
     /// Delete attribute
@@ -1137,13 +1208,7 @@ impl Databases {
         let api_headers = app_json_header!();
 
         let res = client
-            .call(
-                HttpMethod::POST,
-                api_path.as_str(),
-                api_headers,
-                &args,
-                None,
-            )
+            .call(HttpMethod::GET, api_path.as_str(), api_headers, &args, None)
             .await?;
 
         Ok(res.json().await?)
@@ -1215,6 +1280,403 @@ impl Databases {
         Ok(())
     }
 
+    /// Create documents
+    ///
+    /// Create new Documents. Before using this route, you should create a new
+    /// collection resource using either a [server
+    /// integration](https://appwrite.io/docs/server/databases#databasesCreateCollection)
+    /// API or directly from your database console. Each document holds its
+    /// data along with its `$id` and `$permissions`.
+    ///* documents => vec(object)
+    pub async fn create_many_documents(
+        client: &Client,
+        database_id: &str,
+        collection_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<DocumentList, Error> {
+        //const API_PATH: &str = "/databases";
+        let api_path = "/databases/{databaseId}/collections/{collectionId}/documents"
+            .replace("{databaseId}", database_id)
+            .replace("{collectionId}", collection_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::POST,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Upsert document
+    ///
+    /// Create or update a Document. Before using this route, you should create
+    /// a new collection resource using either a [server
+    /// integration](https://appwrite.io/docs/server/databases#databasesCreateCollection)
+    /// API or directly from your database console.
+    ///* data => HashMap<String, Value>
+    ///* permissions => vec(string)?
+    pub async fn upsert_document(
+        client: &Client,
+        database_id: &str,
+        collection_id: &str,
+        document_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<Document, Error> {
+        //const API_PATH: &str = "/databases";
+        let api_path = "/databases/{databaseId}/collections/{collectionId}/documents/{documentId}"
+            .replace("{databaseId}", database_id)
+            .replace("{collectionId}", collection_id)
+            .replace("{documentId}", document_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(HttpMethod::PUT, api_path.as_str(), api_headers, &args, None)
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Upsert documents
+    ///
+    /// Create or update Documents. Each document holds its data along with its
+    /// `$id` and `$permissions`.
+    ///* documents => vec(object)
+    pub async fn upsert_documents(
+        client: &Client,
+        database_id: &str,
+        collection_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<DocumentList, Error> {
+        //const API_PATH: &str = "/databases";
+        let api_path = "/databases/{databaseId}/collections/{collectionId}/documents"
+            .replace("{databaseId}", database_id)
+            .replace("{collectionId}", collection_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(HttpMethod::PUT, api_path.as_str(), api_headers, &args, None)
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Update documents
+    ///
+    /// Update all documents that match your queries, if no queries are
+    /// submitted then all documents are updated. You can pass only specific
+    /// fields to be updated.
+    ///* data => HashMap<String,Value>?
+    ///* queries => vec(string)?
+    pub async fn update_documents(
+        client: &Client,
+        database_id: &str,
+        collection_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<DocumentList, Error> {
+        //const API_PATH: &str = "/databases";
+        let api_path = "/databases/{databaseId}/collections/{collectionId}/documents"
+            .replace("{databaseId}", database_id)
+            .replace("{collectionId}", collection_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::PATCH,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Delete documents
+    ///
+    /// Bulk delete documents using queries, if no queries are passed then all
+    /// documents are deleted.
+    ///* queries => vec(string)?
+    pub async fn delete_documents(
+        client: &Client,
+        database_id: &str,
+        collection_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<DocumentList, Error> {
+        //const API_PATH: &str = "/databases";
+        let api_path = "/databases/{databaseId}/collections/{collectionId}/documents"
+            .replace("{databaseId}", database_id)
+            .replace("{collectionId}", collection_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::DELETE,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Increment document attribute
+    ///
+    /// Increment a specific attribute of a document by a given value. The
+    /// server applies the change atomically.
+    ///* value => number?
+    ///* max => number?
+    pub async fn increment_document_attribute(
+        client: &Client,
+        database_id: &str,
+        collection_id: &str,
+        document_id: &str,
+        attribute: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<Document, Error> {
+        //const API_PATH: &str = "/databases";
+        let api_path = "/databases/{databaseId}/collections/{collectionId}/documents/{documentId}/{attribute}/increment"
+            .replace("{databaseId}", database_id)
+            .replace("{collectionId}", collection_id)
+            .replace("{documentId}", document_id)
+            .replace("{attribute}", attribute);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::PATCH,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Decrement document attribute
+    ///
+    /// Decrement a specific attribute of a document by a given value. The
+    /// server applies the change atomically.
+    ///* value => number?
+    ///* min => number?
+    pub async fn decrement_document_attribute(
+        client: &Client,
+        database_id: &str,
+        collection_id: &str,
+        document_id: &str,
+        attribute: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<Document, Error> {
+        //const API_PATH: &str = "/databases";
+        let api_path = "/databases/{databaseId}/collections/{collectionId}/documents/{documentId}/{attribute}/decrement"
+            .replace("{databaseId}", database_id)
+            .replace("{collectionId}", collection_id)
+            .replace("{documentId}", document_id)
+            .replace("{attribute}", attribute);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::PATCH,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// List documents as `T`
+    ///
+    /// Same as [`Databases::list_documents`], with the data of every document
+    /// deserialized into `T`.
+    ///* queries => vec(string)?
+    pub async fn list_typed_documents<T: DeserializeOwned>(
+        client: &Client,
+        database_id: &str,
+        collection_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<DocumentList<TypedDocument<T>>, Error> {
+        let list = Self::list_documents(client, database_id, collection_id, args).await?;
+
+        Ok(DocumentList {
+            total: list.total,
+            documents: list
+                .documents
+                .into_iter()
+                .map(TypedDocument::from_document)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Create document from `T`
+    ///
+    /// Same as [`Databases::create_documents`], with `data` serialized from a
+    /// struct and the created document read back as `T`.
+    ///* documentId => string
+    ///* permissions => vec(string)?
+    pub async fn create_typed_document<T: Serialize + DeserializeOwned>(
+        client: &Client,
+        database_id: &str,
+        collection_id: &str,
+        data: &T,
+        mut args: HashMap<String, Value>,
+    ) -> Result<TypedDocument<T>, Error> {
+        let data = serde_json::to_value(data)
+            .map_err(|err| Error::Custom(format!("failed to serialize document data: {}", err)))?;
+        if !data.is_object() {
+            return Err(Error::Custom(
+                "document data must serialize to a JSON object".to_string(),
+            ));
+        }
+        args.insert("data".to_string(), data);
+
+        let document = Self::create_documents(client, database_id, collection_id, args).await?;
+
+        TypedDocument::from_document(document)
+    }
+
+    /// Get document as `T`
+    ///
+    /// Same as [`Databases::get_document`], with the document data deserialized
+    /// into `T`.
+    ///* queries => vec(string)?
+    pub async fn get_typed_document<T: DeserializeOwned>(
+        client: &Client,
+        database_id: &str,
+        collection_id: &str,
+        document_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<TypedDocument<T>, Error> {
+        let document =
+            Self::get_document(client, database_id, collection_id, document_id, args).await?;
+
+        TypedDocument::from_document(document)
+    }
+
+    /// Create collection of `T`
+    ///
+    /// Create the collection described by [`AppwriteCollection::schema`] with
+    /// its attributes and indexes.
+    ///* permissions => vec(string)?
+    pub async fn create_collection_of<T: AppwriteCollection>(
+        client: &Client,
+        database_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<Collection, Error> {
+        T::schema().create(client, database_id, args).await
+    }
+
+    /// List documents of `T`
+    ///* queries => vec(string)?
+    pub async fn list_documents_of<T: AppwriteCollection>(
+        client: &Client,
+        database_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<DocumentList<TypedDocument<T>>, Error> {
+        Self::list_typed_documents(client, database_id, T::COLLECTION_ID, args).await
+    }
+
+    /// Create document of `T`
+    ///* documentId => string
+    ///* permissions => vec(string)?
+    pub async fn create_document_of<T: AppwriteCollection>(
+        client: &Client,
+        database_id: &str,
+        data: &T,
+        args: HashMap<String, Value>,
+    ) -> Result<TypedDocument<T>, Error> {
+        Self::create_typed_document(client, database_id, T::COLLECTION_ID, data, args).await
+    }
+
+    /// Get document of `T`
+    ///* queries => vec(string)?
+    pub async fn get_document_of<T: AppwriteCollection>(
+        client: &Client,
+        database_id: &str,
+        document_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<TypedDocument<T>, Error> {
+        Self::get_typed_document(client, database_id, T::COLLECTION_ID, document_id, args).await
+    }
+
+    /// Update document of `T`
+    ///
+    /// Replace the data of the document with `data`.
+    ///* permissions => vec(string)?
+    pub async fn update_document_of<T: AppwriteCollection>(
+        client: &Client,
+        database_id: &str,
+        document_id: &str,
+        data: &T,
+        mut args: HashMap<String, Value>,
+    ) -> Result<TypedDocument<T>, Error> {
+        let data = serde_json::to_value(data)
+            .map_err(|err| Error::Custom(format!("failed to serialize document data: {}", err)))?;
+        args.insert("data".to_string(), data);
+
+        let document =
+            Self::update_document(client, database_id, T::COLLECTION_ID, document_id, args).await?;
+
+        TypedDocument::from_document(document)
+    }
+
+    /// Delete document of `T`
+    pub async fn delete_document_of<T: AppwriteCollection>(
+        client: &Client,
+        database_id: &str,
+        document_id: &str,
+    ) -> Result<(), Error> {
+        Self::delete_document(client, database_id, T::COLLECTION_ID, document_id).await
+    }
+
+    /// Plan migration
+    ///
+    /// Read the live collections, attributes and indexes of the database and
+    /// return the steps bringing it to `schema`, without changing anything.
+    pub async fn plan_migration(
+        client: &Client,
+        schema: &DatabaseSchema,
+        options: &MigrationOptions,
+    ) -> Result<MigrationPlan, Error> {
+        MigrationPlan::new(client, schema, options).await
+    }
+
+    /// Migrate
+    ///
+    /// Plan the migration to `schema` and apply it, unless `options.dry_run`
+    /// is set. Returns the plan.
+    pub async fn migrate(
+        client: &Client,
+        schema: &DatabaseSchema,
+        options: &MigrationOptions,
+    ) -> Result<MigrationPlan, Error> {
+        let plan = MigrationPlan::new(client, schema, options).await?;
+        if !options.dry_run {
+            plan.apply(client, options).await?;
+        }
+        Ok(plan)
+    }
+
     /// List indexes
     ///
     /// List indexes in the collection.
@@ -1300,6 +1762,30 @@ impl Databases {
         Ok(res.json().await?)
     }
 
+    /// Wait for index
+    ///
+    /// Poll [`get_index`](Self::get_index) until the index is `available`.
+    /// Fails when it is `failed` or `stuck`, or after `options.timeout`.
+    pub async fn wait_for_index(
+        client: &Client,
+        database_id: &str,
+        collection_id: &str,
+        key: &str,
+        options: &WaitOptions,
+    ) -> Result<Index, Error> {
+        let resource = format!("index `{}.{}`", collection_id, key);
+        poll(&resource, options, || async {
+            let index = Self::get_index(client, database_id, collection_id, key).await?;
+            Ok(
+                match check_status(&resource, &index.status, &index.error)? {
+                    Poll::Ready(()) => Poll::Ready(index),
+                    Poll::Pending(status) => Poll::Pending(status),
+                },
+            )
+        })
+        .await
+    }
+
     /// Delete index
     ///
     /// Delete an index.
@@ -1331,16 +1817,256 @@ impl Databases {
 
         Ok(res.json().await?)
     }
+
+    /// List transactions
+    ///
+    /// List transactions across all databases.
+    ///* queries => vec(string)?
+    pub async fn list_transactions(
+        client: &Client,
+        args: HashMap<String, Value>,
+    ) -> Result<TransactionList, Error> {
+        const API_PATH: &str = "/databases/transactions";
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(HttpMethod::GET, API_PATH, api_headers, &args, None)
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Create transaction
+    ///
+    /// Create a new transaction. Operations staged under its ID are applied
+    /// together on commit.
+    ///* ttl => number?
+    pub async fn create_transaction(
+        client: &Client,
+        args: HashMap<String, Value>,
+    ) -> Result<Transaction, Error> {
+        const API_PATH: &str = "/databases/transactions";
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(HttpMethod::POST, API_PATH, api_headers, &args, None)
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Get transaction
+    ///
+    /// Get a transaction by its unique ID.
+    pub async fn get_transaction(
+        client: &Client,
+        transaction_id: &str,
+    ) -> Result<Transaction, Error> {
+        let api_path =
+            "/databases/transactions/{transactionId}".replace("{transactionId}", transaction_id);
+
+        let args = HashMap::new();
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(HttpMethod::GET, api_path.as_str(), api_headers, &args, None)
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Update transaction
+    ///
+    /// Commit or roll back a transaction.
+    ///* commit => bool?
+    ///* rollback => bool?
+    pub async fn update_transaction(
+        client: &Client,
+        transaction_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<Transaction, Error> {
+        let api_path =
+            "/databases/transactions/{transactionId}".replace("{transactionId}", transaction_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::PATCH,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Delete transaction
+    ///
+    /// Delete a transaction by its unique ID.
+    pub async fn delete_transaction(client: &Client, transaction_id: &str) -> Result<(), Error> {
+        let api_path =
+            "/databases/transactions/{transactionId}".replace("{transactionId}", transaction_id);
+
+        let args = HashMap::new();
+
+        let api_headers = app_json_header!();
+
+        let _res = client
+            .call(
+                HttpMethod::DELETE,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Create operations
+    ///
+    /// Stage a list of operations in a transaction.
+    ///* operations => vec(object)?
+    pub async fn create_operations(
+        client: &Client,
+        transaction_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<Transaction, Error> {
+        let api_path = "/databases/transactions/{transactionId}/operations"
+            .replace("{transactionId}", transaction_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::POST,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
-    use crate::{client::ClientBuilder, error::Error, id::ID, permission::Permission, role::Role};
+    use serde_json::json;
+
+    use crate::{
+        client::ClientBuilder,
+        enumm::HttpMethod,
+        error::Error,
+        id::ID,
+        permission::Permission,
+        query::Query,
+        role::Role,
+        transport::{MockResponse, MockTransport},
+    };
 
     use super::Databases;
 
-    //#[tokio::test]
+    #[tokio::test]
+    async fn test_counters_and_bulk_by_query() -> Result<(), Error> {
+        let (mock, client) = MockTransport::client()?;
+        let document = json!({"$id": "a", "$sequence": "7", "$collectionId": "books",
+                              "$databaseId": "db", "$createdAt": "", "$updatedAt": "",
+                              "$permissions": [], "stock": 4});
+        mock.on(
+            HttpMethod::PATCH,
+            "/databases/db/collections/books/documents/a/stock/decrement",
+            MockResponse::json(200, document.clone()),
+        )
+        .on(
+            HttpMethod::PATCH,
+            "/databases/db/collections/books/documents",
+            MockResponse::json(200, json!({"total": 1, "documents": [document]})),
+        );
+
+        let decremented = Databases::decrement_document_attribute(
+            &client,
+            "db",
+            "books",
+            "a",
+            "stock",
+            maplit::hashmap! {
+                "value".into() => 1.into(),
+                "min".into() => 0.into(),
+            },
+        )
+        .await?;
+        assert_eq!(decremented.sequence, Some(7));
+        assert_eq!(decremented.data["stock"], json!(4));
+        assert!(!decremented.data.contains_key("$sequence"));
+
+        let updated = Databases::update_documents(
+            &client,
+            "db",
+            "books",
+            maplit::hashmap! {
+                "data".into() => json!({"stock": 4}),
+                "queries".into() => json!([Query::equal("$id".into(), "a".into())]),
+            },
+        )
+        .await?;
+        assert_eq!(updated.documents[0].id, "a");
+
+        let requests = mock.requests();
+        assert_eq!(
+            requests[0].body.as_ref().unwrap(),
+            &json!({"value": 1, "min": 0})
+        );
+        assert_eq!(
+            requests[1].body.as_ref().unwrap()["data"],
+            json!({"stock": 4})
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_document_uses_get() -> Result<(), Error> {
+        let (mock, client) = MockTransport::client()?;
+        mock.on(
+            HttpMethod::GET,
+            "/databases/db/collections/books/documents/a",
+            MockResponse::json(
+                200,
+                json!({"$id": "a", "$collectionId": "books", "$databaseId": "db",
+                       "$createdAt": "", "$updatedAt": "", "$permissions": []}),
+            ),
+        );
+
+        let document = Databases::get_document(
+            &client,
+            "db",
+            "books",
+            "a",
+            maplit::hashmap! {
+                "queries".into() => json!([Query::select("title".into())]),
+            },
+        )
+        .await?;
+        assert_eq!(document.id, "a");
+
+        let requests = mock.requests();
+        assert_eq!(requests[0].method, HttpMethod::GET);
+        assert!(requests[0].body.is_none());
+        assert_eq!(requests[0].query[0].0, "queries[]");
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a running Appwrite server"]
     async fn test_databases() -> Result<(), Error> {
         let client = ClientBuilder::default()
             .set_endpoint("http://127.0.0.1/v1")?
@@ -1435,13 +2161,10 @@ mod tests {
         assert_eq!(create_bool_att.key, "isAdmin");
 
         // ! delete collections
-        let delete_collection =
-            Databases::delete_collection(&client, &update_database.id, &get_col.id).await?;
-        assert_eq!(delete_collection, ());
+        Databases::delete_collection(&client, &update_database.id, &get_col.id).await?;
 
         // ! delete database
-        let delete_database = Databases::delete(&client, &update_database.id).await?;
-        assert_eq!(delete_database, ());
+        Databases::delete(&client, &update_database.id).await?;
 
         Ok(())
     }
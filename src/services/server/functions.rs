@@ -386,11 +386,11 @@ impl Functions {
 
         args.insert(
             "project".into(),
-            get_content_header_value(&client, "project").into(),
+            get_content_header_value(client, "project").into(),
         );
         args.insert(
             "key".into(),
-            get_content_header_value(&client, "key").into(),
+            get_content_header_value(client, "key").into(),
         );
 
         let api_headers = app_json_header!();
@@ -1,9 +1,9 @@
+//! # Locale
+//!
+//! The Locale service allows you to customize your app based on your users&#039;
+//! location.
 use std::collections::HashMap;
 
-///!Locale
-///!
-///! The Locale service allows you to customize your app based on your users&#039;
-///! location.
 use crate::{
     app_json_header,
     client::Client,
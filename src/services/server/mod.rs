@@ -7,5 +7,6 @@ pub mod health;
 pub mod locale;
 pub mod messaging;
 pub mod storage;
+pub mod tables_db;
 pub mod teams;
 pub mod users;
@@ -312,11 +312,11 @@ impl Storage {
 
         args.insert(
             "project".into(),
-            get_content_header_value(&client, "project").into(),
+            get_content_header_value(client, "project").into(),
         );
         args.insert(
             "key".into(),
-            get_content_header_value(&client, "key").into(),
+            get_content_header_value(client, "key").into(),
         );
 
         let api_headers = app_json_header!();
@@ -358,11 +358,11 @@ impl Storage {
 
         args.insert(
             "project".into(),
-            get_content_header_value(&client, "project").into(),
+            get_content_header_value(client, "project").into(),
         );
         args.insert(
             "key".into(),
-            get_content_header_value(&client, "key").into(),
+            get_content_header_value(client, "key").into(),
         );
 
         let api_headers = app_json_header!();
@@ -392,11 +392,11 @@ impl Storage {
 
         args.insert(
             "project".into(),
-            get_content_header_value(&client, "project").into(),
+            get_content_header_value(client, "project").into(),
         );
         args.insert(
             "key".into(),
-            get_content_header_value(&client, "key").into(),
+            get_content_header_value(client, "key").into(),
         );
 
         let api_headers = app_json_header!();
@@ -0,0 +1,1908 @@
+//! # TablesDB
+//!
+//! The TablesDB service allows you to create structured tables of rows,
+//! query and filter lists of rows. It is the successor of the
+//! [`Databases`](super::databases::Databases) service, with tables, columns
+//! and rows in place of collections, attributes and documents.
+use std::collections::HashMap;
+
+use crate::{
+    app_json_header,
+    client::Client,
+    enumm::HttpMethod,
+    error::Error,
+    models::{
+        column::Column, column_boolean::ColumnBoolean, column_datetime::ColumnDateTime,
+        column_email::ColumnEmail, column_enum::ColumnEnum, column_float::ColumnFloat,
+        column_index::ColumnIndex, column_index_list::ColumnIndexList,
+        column_integer::ColumnInteger, column_ip::ColumnIp, column_list::ColumnList,
+        column_relationship::ColumnRelationship, column_string::ColumnString,
+        column_url::ColumnUrl, database::Database, database_list::DatabaseList, row::Row,
+        row_list::RowList, table::Table, table_list::TableList, transaction::Transaction,
+        transaction_list::TransactionList, typed_row::TypedRow,
+    },
+    wait::{check_status, poll, Poll, WaitOptions},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+pub struct TablesDb;
+
+impl TablesDb {
+    /// List databases
+    ///
+    /// Get a list of all databases from the current Appwrite project. You can use
+    /// the search parameter to filter your results.
+    ///* queries => vec(string)?
+    ///* search => string?
+    pub async fn list(
+        client: &Client,
+        args: HashMap<String, Value>,
+    ) -> Result<DatabaseList, Error> {
+        const API_PATH: &str = "/tablesdb";
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(HttpMethod::GET, API_PATH, api_headers, &args, None)
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Create database
+    ///
+    /// Create a new Database.
+    ///
+    ///* databaseId => string
+    ///* name => string
+    ///* enabled => bool?
+    pub async fn create(client: &Client, args: HashMap<String, Value>) -> Result<Database, Error> {
+        const API_PATH: &str = "/tablesdb";
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(HttpMethod::POST, API_PATH, api_headers, &args, None)
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Get database
+    ///
+    /// Get a database by its unique ID. This endpoint response returns a JSON
+    /// object with the database metadata.
+    pub async fn get(client: &Client, database_id: &str) -> Result<Database, Error> {
+        let api_path = "/tablesdb/{databaseId}".replace("{databaseId}", database_id);
+
+        let args = HashMap::new();
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(HttpMethod::GET, api_path.as_str(), api_headers, &args, None)
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Update database
+    ///
+    /// Update a database by its unique ID.
+    ///* name => string
+    ///* enabled => bool?
+    pub async fn update(
+        client: &Client,
+        database_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<Database, Error> {
+        let api_path = "/tablesdb/{databaseId}".replace("{databaseId}", database_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(HttpMethod::PUT, api_path.as_str(), api_headers, &args, None)
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Delete database
+    ///
+    /// Delete a database by its unique ID. Only API keys with with databases.write
+    /// scope can delete a database.
+    pub async fn delete(client: &Client, database_id: &str) -> Result<(), Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}".replace("{databaseId}", database_id);
+
+        let args = HashMap::new();
+
+        let api_headers = app_json_header!();
+
+        let _res = client
+            .call(
+                HttpMethod::DELETE,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// List tables
+    ///
+    /// Get a list of all tables that belong to the provided databaseId. You
+    /// can use the search parameter to filter your results.
+    ///* search => string?
+    ///* queries => vec(string)?
+    pub async fn list_tables(
+        client: &Client,
+        database_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<TableList, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables".replace("{databaseId}", database_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(HttpMethod::GET, api_path.as_str(), api_headers, &args, None)
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Create table
+    ///
+    /// Create a new Table. Before using this route, you should create a new
+    /// database resource using either a [server
+    /// integration](https://appwrite.io/docs/references/cloud/server-rest/tablesdb#createTable)
+    /// API or directly from your database console.
+    ///* tableId => string
+    ///* name => string
+    ///* permissions => option(vec)?
+    ///* rowSecurity => bool?
+    ///* enabled => bool?
+    pub async fn create_table(
+        client: &Client,
+        database_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<Table, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables".replace("{databaseId}", database_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::POST,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Get table
+    ///
+    /// Get a table by its unique ID. This endpoint response returns a JSON
+    /// object with the table metadata.
+    pub async fn get_table(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+    ) -> Result<Table, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id);
+
+        let args = HashMap::new();
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(HttpMethod::GET, api_path.as_str(), api_headers, &args, None)
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Wait for table ready
+    ///
+    /// Poll [`get_table`](Self::get_table) until every column
+    /// and index of the table is `available`. Fails when one of them is
+    /// `failed` or `stuck`, or after `options.timeout`.
+    pub async fn wait_for_table_ready(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        options: &WaitOptions,
+    ) -> Result<Table, Error> {
+        let resource = format!("table `{}`", table_id);
+        poll(&resource, options, || async {
+            let table = Self::get_table(client, database_id, table_id).await?;
+            let statuses = table
+                .columns
+                .iter()
+                .map(|column| {
+                    (
+                        format!("column `{}.{}`", table_id, column.key()),
+                        column.status(),
+                        column.error(),
+                    )
+                })
+                .chain(table.indexes.iter().map(|index| {
+                    (
+                        format!("index `{}.{}`", table_id, index.key),
+                        index.status.as_str(),
+                        index.error.as_str(),
+                    )
+                }));
+            for (resource, status, error) in statuses {
+                if let Poll::Pending(status) = check_status(&resource, status, error)? {
+                    return Ok(Poll::Pending(format!("{} {}", resource, status)));
+                }
+            }
+            Ok(Poll::Ready(table))
+        })
+        .await
+    }
+
+    /// Update table
+    ///
+    /// Update a table by its unique ID.
+    ///* name => string
+    ///* permissions => vec(string)?
+    ///* rowSecurity => bool?
+    ///* enabled => bool?
+    pub async fn update_table(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<Table, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(HttpMethod::PUT, api_path.as_str(), api_headers, &args, None)
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Delete table
+    ///
+    /// Delete a table by its unique ID. Only users with write permissions
+    /// have access to delete this resource.
+    pub async fn delete_table(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+    ) -> Result<(), Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id);
+
+        let args = HashMap::new();
+
+        let api_headers = app_json_header!();
+
+        let _res = client
+            .call(
+                HttpMethod::DELETE,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// List columns
+    ///
+    /// List columns in the table.
+    ///* queries => vec(string)?
+    pub async fn list_columns(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<ColumnList, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/columns"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(HttpMethod::GET, api_path.as_str(), api_headers, &args, None)
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Create boolean column
+    ///
+    /// Create a boolean column.
+    ///
+    ///* key => string
+    ///* required => bool
+    ///* default => bool?
+    ///* array => bool?
+    pub async fn create_boolean_column(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<ColumnBoolean, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/columns/boolean"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::POST,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Update boolean column
+    ///
+    ///  Update a boolean column. Changing the `default` value will not update
+    /// already existing rows.
+    ///* required => bool
+    ///* default => bool?
+    pub async fn update_boolean_column(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        key: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<ColumnBoolean, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/columns/boolean/{key}"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id)
+            .replace("{key}", key);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::PATCH,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Create datetime column
+    ///
+    ///  Create a date time column according to the ISO 8601 standard.
+    ///* key => string
+    ///* required => bool
+    ///* default => bool?
+    ///* array => bool?
+    pub async fn create_date_time_column(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<ColumnDateTime, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/columns/datetime"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::POST,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Update dateTime column
+    ///
+    ///  Update a date time column. Changing the `default` value will not update
+    /// already existing rows.
+    ///* required => bool
+    ///* default => bool?
+    pub async fn update_date_time_column(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        key: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<ColumnDateTime, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/columns/datetime/{key}"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id)
+            .replace("{key}", key);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::PATCH,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Create email column
+    ///
+    /// Create an email column.
+    ///
+    ///* key => string
+    ///* required => bool
+    ///* default => bool?
+    ///* array => bool?
+    pub async fn create_email_column(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<ColumnEmail, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/columns/email"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::POST,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Update email column
+    ///
+    /// Update an email column. Changing the `default` value will not update
+    /// already existing rows.
+    ///
+    ///* required => bool
+    ///* default => string?
+    pub async fn update_email_column(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        key: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<ColumnEmail, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/columns/email/{key}"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id)
+            .replace("{key}", key);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::PATCH,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Create enum column
+    ///
+    ///* key => string
+    ///* elements => vec(string)
+    ///* required => bool
+    ///* default => bool?
+    ///* array => bool?
+    pub async fn create_enum_column(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<ColumnEnum, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/columns/enum"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::POST,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Update enum column
+    ///
+    /// Update an enum column. Changing the `default` value will not update
+    /// already existing rows.
+    ///
+    ///* elements => vec(string)
+    ///* required => bool
+    ///* default => bool?
+    pub async fn update_enum_column(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        key: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<ColumnEnum, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/columns/enum/{key}"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id)
+            .replace("{key}", key);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::PATCH,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Create float column
+    ///
+    /// Create a float column. Optionally, minimum and maximum values can be
+    /// provided.
+    ///
+    ///* key => string
+    ///* required => bool
+    ///* min => float?
+    ///* max => float?
+    ///* default => float?
+    ///* array => bool?
+    pub async fn create_float_column(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<ColumnFloat, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/columns/float"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::POST,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Update float column
+    ///
+    /// Update a float column. Changing the `default` value will not update
+    /// already existing rows.
+    ///
+    ///* required => bool
+    ///* min => float?
+    ///* max => float?
+    ///* default => float?
+    pub async fn update_float_column(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        key: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<ColumnFloat, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/columns/float/{key}"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id)
+            .replace("{key}", key);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::PATCH,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Create integer column
+    ///
+    /// Create an integer column. Optionally, minimum and maximum values can be
+    /// provided.
+    ///
+    ///* key => string
+    ///* required => bool
+    ///* min => number?
+    ///* max => number?
+    ///* default => number?
+    ///* array => bool?
+    pub async fn create_integer_column(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<ColumnInteger, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/columns/integer"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::POST,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Update integer column
+    ///
+    /// Update an integer column. Changing the `default` value will not update
+    /// already existing rows.
+    ///
+    ///* required => bool
+    ///* min => number?
+    ///* max => number?
+    ///* default => number?
+    pub async fn update_integer_column(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        key: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<ColumnInteger, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/columns/integer/{key}"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id)
+            .replace("{key}", key);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::PATCH,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Create IP address column
+    ///
+    /// Create IP address column.
+    ///
+    ///* key => string
+    ///* required => bool
+    ///* default => string?
+    ///* array => bool?
+    pub async fn create_ip_column(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<ColumnIp, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/columns/ip"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::POST,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Update IP address column
+    ///
+    /// Update an ip column. Changing the `default` value will not update
+    /// already existing rows.
+    ///
+    ///* required => bool
+    ///* default => string?
+    pub async fn update_ip_column(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        key: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<ColumnIp, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/columns/ip/{key}"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id)
+            .replace("{key}", key);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::PATCH,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Create relationship column
+    ///
+    /// Create relationship column. [Learn more about relationship
+    /// columns](https://appwrite.io/docs/products/databases/relationships).
+    ///
+    ///* relatedTableId => string
+    ///* type => RelationshipType
+    ///* twoWay => bool?
+    ///* key => string?
+    ///* twoWayKey => string?
+    ///* onDelete => RelationMutate?
+    pub async fn create_relationship_column(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<ColumnRelationship, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/columns/relationship"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::POST,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Create string column
+    ///
+    /// Create a string column.
+    ///
+    ///* key => string
+    ///* size => number
+    ///* required => bool
+    ///* default => string?
+    ///* array => bool?
+    ///* encrypt => bool?
+    pub async fn create_string_column(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<ColumnString, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/columns/string"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::POST,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Update string column
+    ///
+    /// Update a string column. Changing the `default` value will not update
+    /// already existing rows.
+    ///
+    ///* required => bool
+    ///* default => string?
+    pub async fn update_string_column(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        key: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<ColumnString, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/columns/string/{key}"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id)
+            .replace("{key}", key);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::PATCH,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Create URL column
+    ///
+    /// Create a URL column.
+    ///
+    ///* key => string
+    ///* required => bool
+    ///* default => string?
+    ///* array => bool?
+    pub async fn create_url_column(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<ColumnUrl, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/columns/url"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::POST,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Update URL column
+    ///
+    /// Update an url column. Changing the `default` value will not update
+    /// already existing rows.
+    ///
+    ///* required => bool
+    ///* default => string?
+    pub async fn update_url_column(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        key: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<ColumnUrl, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/columns/url/{key}"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id)
+            .replace("{key}", key);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::PATCH,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Get column
+    ///
+    /// Get column by ID.
+    pub async fn get_column(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        key: &str,
+    ) -> Result<Column, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/columns/{key}"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id)
+            .replace("{key}", key);
+
+        let args = HashMap::new();
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(HttpMethod::GET, api_path.as_str(), api_headers, &args, None)
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Wait for column
+    ///
+    /// Poll [`get_column`](Self::get_column) until the column is
+    /// `available`. Fails when it is `failed` or `stuck`, or after
+    /// `options.timeout`.
+    pub async fn wait_for_column(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        key: &str,
+        options: &WaitOptions,
+    ) -> Result<Column, Error> {
+        let resource = format!("column `{}.{}`", table_id, key);
+        poll(&resource, options, || async {
+            let column = Self::get_column(client, database_id, table_id, key).await?;
+            Ok(
+                match check_status(&resource, column.status(), column.error())? {
+                    Poll::Ready(()) => Poll::Ready(column),
+                    Poll::Pending(status) => Poll::Pending(status),
+                },
+            )
+        })
+        .await
+    }
+
+    /// Delete column
+    ///
+    /// Deletes a column.
+    pub async fn delete_column(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        key: &str,
+    ) -> Result<(), Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/columns/{key}"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id)
+            .replace("{key}", key);
+
+        let args = HashMap::new();
+
+        let api_headers = app_json_header!();
+
+        let _res = client
+            .call(
+                HttpMethod::DELETE,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Update relationship column
+    ///
+    /// Update relationship column. [Learn more about relationship
+    /// columns](https://appwrite.io/docs/products/databases/relationships).
+    ///
+    ///* onDelete => RelationMutate?
+    pub async fn update_relationship_column(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        key: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<ColumnRelationship, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/columns/{key}/relationship"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id)
+            .replace("{key}", key);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::PATCH,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// List rows
+    ///
+    /// Get a list of all the user's rows in a given table. You can use
+    /// the query params to filter your results.
+    ///* queries => vec(string)?
+    pub async fn list_rows(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<RowList, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/rows"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(HttpMethod::GET, api_path.as_str(), api_headers, &args, None)
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Create row
+    ///
+    /// Create a new Row. Before using this route, you should create a new
+    /// table resource using either a [server
+    /// integration](https://appwrite.io/docs/references/cloud/server-rest/tablesdb#createTable)
+    /// API or directly from your database console.
+    ///* rowId => string
+    ///* data => HashMap<String, Value>
+    ///* permissions => vec(string)?
+    pub async fn create_row(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<Row, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/rows"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::POST,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Get row
+    ///
+    /// Get a row by its unique ID. This endpoint response returns a JSON
+    /// object with the row data.
+    ///* queries => vec(string)?
+    pub async fn get_row(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        row_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<Row, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/rows/{rowId}"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id)
+            .replace("{rowId}", row_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(HttpMethod::GET, api_path.as_str(), api_headers, &args, None)
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Update row
+    ///
+    /// Update a row by its unique ID. Using the patch method you can pass
+    /// only specific fields that will get updated.
+    ///* data => HashMap<String,Value>?
+    ///* permissions => vec(string)?
+    pub async fn update_row(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        row_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<Row, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/rows/{rowId}"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id)
+            .replace("{rowId}", row_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::PATCH,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Delete row
+    ///
+    /// Delete a row by its unique ID.
+    pub async fn delete_row(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        row_id: &str,
+    ) -> Result<(), Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/rows/{rowId}"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id)
+            .replace("{rowId}", row_id);
+
+        let args = HashMap::new();
+
+        let api_headers = app_json_header!();
+
+        let _res = client
+            .call(
+                HttpMethod::DELETE,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Create rows
+    ///
+    /// Create new Rows. Before using this route, you should create a new
+    /// table resource using either a [server
+    /// integration](https://appwrite.io/docs/references/cloud/server-rest/tablesdb#createTable)
+    /// API or directly from your database console. Each row holds its
+    /// data along with its `$id` and `$permissions`.
+    ///* rows => vec(object)
+    pub async fn create_rows(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<RowList, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/rows"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::POST,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Upsert row
+    ///
+    /// Create or update a Row. Before using this route, you should create
+    /// a new table resource using either a [server
+    /// integration](https://appwrite.io/docs/references/cloud/server-rest/tablesdb#createTable)
+    /// API or directly from your database console.
+    ///* data => HashMap<String, Value>
+    ///* permissions => vec(string)?
+    pub async fn upsert_row(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        row_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<Row, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/rows/{rowId}"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id)
+            .replace("{rowId}", row_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(HttpMethod::PUT, api_path.as_str(), api_headers, &args, None)
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Upsert rows
+    ///
+    /// Create or update Rows. Each row holds its data along with its
+    /// `$id` and `$permissions`.
+    ///* rows => vec(object)
+    pub async fn upsert_rows(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<RowList, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/rows"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(HttpMethod::PUT, api_path.as_str(), api_headers, &args, None)
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Update rows
+    ///
+    /// Update all rows that match your queries, if no queries are
+    /// submitted then all rows are updated. You can pass only specific
+    /// fields to be updated.
+    ///* data => HashMap<String,Value>?
+    ///* queries => vec(string)?
+    pub async fn update_rows(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<RowList, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/rows"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::PATCH,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Delete rows
+    ///
+    /// Bulk delete rows using queries, if no queries are passed then all
+    /// rows are deleted.
+    ///* queries => vec(string)?
+    pub async fn delete_rows(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<RowList, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/rows"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::DELETE,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Increment row column
+    ///
+    /// Increment a specific column of a row by a given value. The
+    /// server applies the change atomically.
+    ///* value => number?
+    ///* max => number?
+    pub async fn increment_row_column(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        row_id: &str,
+        column: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<Row, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/rows/{rowId}/{column}/increment"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id)
+            .replace("{rowId}", row_id)
+            .replace("{column}", column);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::PATCH,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Decrement row column
+    ///
+    /// Decrement a specific column of a row by a given value. The
+    /// server applies the change atomically.
+    ///* value => number?
+    ///* min => number?
+    pub async fn decrement_row_column(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        row_id: &str,
+        column: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<Row, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/rows/{rowId}/{column}/decrement"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id)
+            .replace("{rowId}", row_id)
+            .replace("{column}", column);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::PATCH,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// List rows as `T`
+    ///
+    /// Same as [`TablesDb::list_rows`], with the data of every row
+    /// deserialized into `T`.
+    ///* queries => vec(string)?
+    pub async fn list_typed_rows<T: DeserializeOwned>(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<RowList<TypedRow<T>>, Error> {
+        let list = Self::list_rows(client, database_id, table_id, args).await?;
+
+        Ok(RowList {
+            total: list.total,
+            rows: list
+                .rows
+                .into_iter()
+                .map(TypedRow::from_row)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Create row from `T`
+    ///
+    /// Same as [`TablesDb::create_row`], with `data` serialized from a
+    /// struct and the created row read back as `T`.
+    ///* rowId => string
+    ///* permissions => vec(string)?
+    pub async fn create_typed_row<T: Serialize + DeserializeOwned>(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        data: &T,
+        mut args: HashMap<String, Value>,
+    ) -> Result<TypedRow<T>, Error> {
+        let data = serde_json::to_value(data)
+            .map_err(|err| Error::Custom(format!("failed to serialize row data: {}", err)))?;
+        if !data.is_object() {
+            return Err(Error::Custom(
+                "row data must serialize to a JSON object".to_string(),
+            ));
+        }
+        args.insert("data".to_string(), data);
+
+        let row = Self::create_row(client, database_id, table_id, args).await?;
+
+        TypedRow::from_row(row)
+    }
+
+    /// Get row as `T`
+    ///
+    /// Same as [`TablesDb::get_row`], with the row data deserialized
+    /// into `T`.
+    ///* queries => vec(string)?
+    pub async fn get_typed_row<T: DeserializeOwned>(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        row_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<TypedRow<T>, Error> {
+        let row = Self::get_row(client, database_id, table_id, row_id, args).await?;
+
+        TypedRow::from_row(row)
+    }
+
+    /// List indexes
+    ///
+    /// List indexes in the table.
+    ///* queries => vec(string)?
+    pub async fn list_indexes(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<ColumnIndexList, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/indexes"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(HttpMethod::GET, api_path.as_str(), api_headers, &args, None)
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Create index
+    ///
+    /// Creates an index on the columns listed. Your index should include all
+    /// the columns you will query in a single request.
+    /// Indexes can be `key`, `fulltext`, and `unique`.
+    ///* key => string
+    ///* type => IndexType
+    ///* columns => vec(string)
+    ///* orders => vec(string)?
+    pub async fn create_index(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<ColumnIndex, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/indexes"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::POST,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Get index
+    ///
+    /// Get index by ID.
+    pub async fn get_index(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        key: &str,
+    ) -> Result<ColumnIndex, Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/indexes/{key}"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id)
+            .replace("{key}", key);
+
+        let args = HashMap::new();
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(HttpMethod::GET, api_path.as_str(), api_headers, &args, None)
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Wait for index
+    ///
+    /// Poll [`get_index`](Self::get_index) until the index is `available`.
+    /// Fails when it is `failed` or `stuck`, or after `options.timeout`.
+    pub async fn wait_for_index(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        key: &str,
+        options: &WaitOptions,
+    ) -> Result<ColumnIndex, Error> {
+        let resource = format!("index `{}.{}`", table_id, key);
+        poll(&resource, options, || async {
+            let index = Self::get_index(client, database_id, table_id, key).await?;
+            Ok(
+                match check_status(&resource, &index.status, &index.error)? {
+                    Poll::Ready(()) => Poll::Ready(index),
+                    Poll::Pending(status) => Poll::Pending(status),
+                },
+            )
+        })
+        .await
+    }
+
+    /// Delete index
+    ///
+    /// Delete an index.
+    pub async fn delete_index(
+        client: &Client,
+        database_id: &str,
+        table_id: &str,
+        key: &str,
+    ) -> Result<(), Error> {
+        //const API_PATH: &str = "/tablesdb";
+        let api_path = "/tablesdb/{databaseId}/tables/{tableId}/indexes/{key}"
+            .replace("{databaseId}", database_id)
+            .replace("{tableId}", table_id)
+            .replace("{key}", key);
+
+        let args = HashMap::new();
+
+        let api_headers = app_json_header!();
+
+        let _res = client
+            .call(
+                HttpMethod::DELETE,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// List transactions
+    ///
+    /// List transactions across all databases.
+    ///* queries => vec(string)?
+    pub async fn list_transactions(
+        client: &Client,
+        args: HashMap<String, Value>,
+    ) -> Result<TransactionList, Error> {
+        const API_PATH: &str = "/tablesdb/transactions";
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(HttpMethod::GET, API_PATH, api_headers, &args, None)
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Create transaction
+    ///
+    /// Create a new transaction. Operations staged under its ID are applied
+    /// together on commit.
+    ///* ttl => number?
+    pub async fn create_transaction(
+        client: &Client,
+        args: HashMap<String, Value>,
+    ) -> Result<Transaction, Error> {
+        const API_PATH: &str = "/tablesdb/transactions";
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(HttpMethod::POST, API_PATH, api_headers, &args, None)
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Get transaction
+    ///
+    /// Get a transaction by its unique ID.
+    pub async fn get_transaction(
+        client: &Client,
+        transaction_id: &str,
+    ) -> Result<Transaction, Error> {
+        let api_path =
+            "/tablesdb/transactions/{transactionId}".replace("{transactionId}", transaction_id);
+
+        let args = HashMap::new();
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(HttpMethod::GET, api_path.as_str(), api_headers, &args, None)
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Update transaction
+    ///
+    /// Commit or roll back a transaction.
+    ///* commit => bool?
+    ///* rollback => bool?
+    pub async fn update_transaction(
+        client: &Client,
+        transaction_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<Transaction, Error> {
+        let api_path =
+            "/tablesdb/transactions/{transactionId}".replace("{transactionId}", transaction_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::PATCH,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+
+    /// Delete transaction
+    ///
+    /// Delete a transaction by its unique ID.
+    pub async fn delete_transaction(client: &Client, transaction_id: &str) -> Result<(), Error> {
+        let api_path =
+            "/tablesdb/transactions/{transactionId}".replace("{transactionId}", transaction_id);
+
+        let args = HashMap::new();
+
+        let api_headers = app_json_header!();
+
+        let _res = client
+            .call(
+                HttpMethod::DELETE,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Create operations
+    ///
+    /// Stage a list of operations in a transaction.
+    ///* operations => vec(object)?
+    pub async fn create_operations(
+        client: &Client,
+        transaction_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<Transaction, Error> {
+        let api_path = "/tablesdb/transactions/{transactionId}/operations"
+            .replace("{transactionId}", transaction_id);
+
+        let api_headers = app_json_header!();
+
+        let res = client
+            .call(
+                HttpMethod::POST,
+                api_path.as_str(),
+                api_headers,
+                &args,
+                None,
+            )
+            .await?;
+
+        Ok(res.json().await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::StreamExt;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        paginate::{paginate, PageOptions},
+        transport::{MockResponse, MockTransport},
+    };
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Book {
+        title: String,
+    }
+
+    fn page(ids: &[&str]) -> MockResponse {
+        let rows: Vec<_> = ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| {
+                json!({"$id": id, "$sequence": i + 1, "$tableId": "books", "$databaseId": "db",
+                       "$createdAt": "", "$updatedAt": "", "$permissions": [], "title": id})
+            })
+            .collect();
+        MockResponse::json(200, json!({"total": 3, "rows": rows}))
+    }
+
+    #[tokio::test]
+    async fn test_paginate_typed_rows() -> Result<(), Error> {
+        let (mock, client) = MockTransport::client()?;
+        let path = "/tablesdb/db/tables/books/rows";
+        mock.on(HttpMethod::GET, path, page(&["a", "b"]))
+            .on(HttpMethod::GET, path, page(&["c"]));
+
+        let rows: Vec<TypedRow<Book>> = paginate(
+            HashMap::new(),
+            PageOptions {
+                page_size: 2,
+                ..Default::default()
+            },
+            |args| TablesDb::list_typed_rows(&client, "db", "books", args),
+        )
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+        let titles: Vec<_> = rows.iter().map(|row| row.data.title.as_str()).collect();
+        assert_eq!(titles, ["a", "b", "c"]);
+        assert_eq!(
+            (rows[1].table_id.as_str(), rows[1].sequence),
+            ("books", Some(2))
+        );
+        assert_eq!(mock.requests().len(), 2);
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
-use serde_json::{Map, Value};
-///! # Teams
-///! The Teams service allows you to group users of your project and to enable
-///! them to share read and write access to your project resources
+//! # Teams
+//! The Teams service allows you to group users of your project and to enable
+//! them to share read and write access to your project resources
+use serde_json::Value;
 use std::collections::HashMap;
 
 use crate::{
@@ -372,16 +372,14 @@ impl Teams {
 mod tests {
     use std::collections::HashMap;
 
-    use serde_json::{json, Map, Value};
+    use serde_json::{Map, Value};
 
-    use crate::{
-        client::ClientBuilder, error::Error, id::ID, query::Query, role::Role,
-        services::server::users::Users,
-    };
+    use crate::{client::ClientBuilder, error::Error, id::ID, services::server::users::Users};
 
     use super::Teams;
 
-    // #[tokio::test]
+    #[tokio::test]
+    #[ignore = "needs a running Appwrite server"]
     async fn test_teams() -> Result<(), Error> {
         let client = ClientBuilder::default()
             .set_endpoint("http://127.0.0.1/v1")?
@@ -485,17 +483,12 @@ mod tests {
         assert_eq!(update_team.clone().name, "chicago org");
 
         // ! remove members
-        let remove_member =
-            Teams::delete_memberships(&client, &create_team.id, &membership_ids[0]).await?;
-        assert_eq!(remove_member, ());
+        Teams::delete_memberships(&client, &create_team.id, &membership_ids[0]).await?;
 
         // ! delete team and members
-        let delete_team = Teams::delete(&client, &create_team.id).await?;
-        assert_eq!(delete_team, ());
-        let delete_user1 = Users::delete(&client, &create_user1.id).await?;
-        assert_eq!(delete_user1, ());
-        let delete_user2 = Users::delete(&client, &create_user2.id).await?;
-        assert_eq!(delete_user2, ());
+        Teams::delete(&client, &create_team.id).await?;
+        Users::delete(&client, &create_user1.id).await?;
+        Users::delete(&client, &create_user2.id).await?;
 
         Ok(())
     }
@@ -1113,7 +1113,8 @@ mod tests {
 
     use super::Users;
 
-    // #[tokio::test]
+    #[tokio::test]
+    #[ignore = "needs a running Appwrite server"]
     async fn test_user() -> Result<(), Error> {
         let client = ClientBuilder::default()
             .set_endpoint("http://127.0.0.1/v1")?
@@ -1195,8 +1196,7 @@ mod tests {
         assert_eq!(get_prefs.data, ans);
 
         // ! delete user
-        let delete_user = Users::delete(&client, &user_res.id).await?;
-        assert_eq!(delete_user, ());
+        Users::delete(&client, &user_res.id).await?;
 
         // ! create argon2 user
         let argon2_user_res = Users::create_argon2_user(
@@ -1223,11 +1223,9 @@ mod tests {
         .await?;
         assert_eq!(sha_user_res.email, "shaemail@email.com");
 
-        let delete_argon_user = Users::delete(&client, &argon2_user_res.id).await?;
-        assert_eq!(delete_argon_user, ());
+        Users::delete(&client, &argon2_user_res.id).await?;
 
-        let delete_sha_user = Users::delete(&client, &sha_user_res.id).await?;
-        assert_eq!(delete_sha_user, ());
+        Users::delete(&client, &sha_user_res.id).await?;
 
         Ok(())
     }
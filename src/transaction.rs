@@ -0,0 +1,336 @@
+//! # Transaction
+//!
+//! Stage document and row writes under a server side transaction and apply
+//! them all at once. [`TransactionHandle::client`] is a derived client that
+//! tags every document and row call with the `transactionId`, so the usual
+//! [`Databases`] and [`TablesDb`] methods take part in the transaction.
+//!
+//! A handle dropped without [`commit`](TransactionHandle::commit) or
+//! [`rollback`](TransactionHandle::rollback) rolls the transaction back in
+//! the background. Outside of a tokio runtime the transaction is left to
+//! expire after its `ttl`.
+//! ```no_run
+//! use serde_json::json;
+//! use unofficial_appwrite::{client::ClientBuilder, services::server::databases::Databases};
+//!
+//! # async fn run() -> Result<(), unofficial_appwrite::error::Error> {
+//! let client = ClientBuilder::from_env()?.build()?;
+//! let tx = Databases::begin_transaction(&client, Default::default()).await?;
+//!
+//! let mut order = std::collections::HashMap::new();
+//! order.insert("documentId".to_string(), json!("order-1"));
+//! order.insert("data".to_string(), json!({"total": 30}));
+//! Databases::create_documents(tx.client(), "shop", "orders", order).await?;
+//!
+//! let mut item = std::collections::HashMap::new();
+//! item.insert("documentId".to_string(), json!("order-1-1"));
+//! item.insert("data".to_string(), json!({"order": "order-1", "price": 30}));
+//! Databases::create_documents(tx.client(), "shop", "line_items", item).await?;
+//!
+//! tx.commit().await?;
+//! # Ok(())
+//! # }
+//! ```
+use std::{collections::HashMap, sync::Arc};
+
+use futures_util::future::BoxFuture;
+use serde_json::{json, Value};
+
+use crate::{
+    client::Client,
+    enumm::HttpMethod,
+    error::Error,
+    interceptor::{Interceptor, RequestFlow},
+    models::transaction::Transaction,
+    services::server::{databases::Databases, tables_db::TablesDb},
+    transport::{RequestBody, TransportRequest},
+};
+
+/// Service owning the transaction endpoints.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Api {
+    Databases,
+    TablesDb,
+}
+
+impl Api {
+    async fn update_transaction(
+        self,
+        client: &Client,
+        transaction_id: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<Transaction, Error> {
+        match self {
+            Api::Databases => Databases::update_transaction(client, transaction_id, args).await,
+            Api::TablesDb => TablesDb::update_transaction(client, transaction_id, args).await,
+        }
+    }
+}
+
+/// Adds `transactionId` to document and row calls, unless the caller set one.
+#[derive(Debug)]
+struct TransactionTag {
+    transaction_id: String,
+}
+
+impl TransactionTag {
+    fn applies_to(path: &str) -> bool {
+        (path.starts_with("/databases/") && path.contains("/documents"))
+            || (path.starts_with("/tablesdb/") && path.contains("/rows"))
+    }
+}
+
+impl Interceptor for TransactionTag {
+    fn on_request<'a>(
+        &'a self,
+        request: &'a mut TransportRequest,
+    ) -> BoxFuture<'a, Result<RequestFlow, Error>> {
+        Box::pin(async move {
+            if !Self::applies_to(&request.path) {
+                return Ok(RequestFlow::Continue);
+            }
+            match &mut request.body {
+                RequestBody::Json(Value::Object(params)) => {
+                    params
+                        .entry("transactionId")
+                        .or_insert_with(|| json!(self.transaction_id));
+                }
+                RequestBody::Empty
+                    if request.method == HttpMethod::GET
+                        && !request.url.contains("transactionId=") =>
+                {
+                    let pair = url::form_urlencoded::Serializer::new(String::new())
+                        .append_pair("transactionId", &self.transaction_id)
+                        .finish();
+                    let separator = if request.url.contains('?') { '&' } else { '?' };
+                    request.url = format!("{}{}{}", request.url, separator, pair);
+                }
+                _ => {}
+            }
+            Ok(RequestFlow::Continue)
+        })
+    }
+}
+
+/// Open transaction, see [`transaction`](crate::transaction).
+#[derive(Debug)]
+pub struct TransactionHandle {
+    api: Api,
+    /// Client the handle was created from, used to commit and roll back.
+    base: Client,
+    /// `base` tagging document and row calls with the transaction ID.
+    client: Client,
+    transaction: Transaction,
+    finished: bool,
+}
+
+impl TransactionHandle {
+    fn new(api: Api, base: &Client, transaction: Transaction) -> Self {
+        let client = base.with_interceptor(Arc::new(TransactionTag {
+            transaction_id: transaction.id.clone(),
+        }));
+        Self {
+            api,
+            base: base.clone(),
+            client,
+            transaction,
+            finished: false,
+        }
+    }
+
+    /// Transaction ID.
+    pub fn id(&self) -> &str {
+        &self.transaction.id
+    }
+
+    /// Transaction as returned when it was created.
+    pub fn transaction(&self) -> &Transaction {
+        &self.transaction
+    }
+
+    /// Client whose document and row calls are staged in the transaction.
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Apply every staged operation.
+    pub async fn commit(self) -> Result<Transaction, Error> {
+        self.finish("commit").await
+    }
+
+    /// Discard every staged operation.
+    pub async fn rollback(self) -> Result<Transaction, Error> {
+        self.finish("rollback").await
+    }
+
+    /// On error the handle is dropped unfinished and rolls back.
+    async fn finish(mut self, action: &str) -> Result<Transaction, Error> {
+        let mut args = HashMap::new();
+        args.insert(action.to_string(), json!(true));
+        let transaction = self
+            .api
+            .update_transaction(&self.base, &self.transaction.id, args)
+            .await?;
+        self.finished = true;
+        Ok(transaction)
+    }
+}
+
+impl Drop for TransactionHandle {
+    fn drop(&mut self) {
+        if self.finished {
+            return;
+        }
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let (api, client, transaction_id) =
+            (self.api, self.base.clone(), self.transaction.id.clone());
+        runtime.spawn(async move {
+            let mut args = HashMap::new();
+            args.insert("rollback".to_string(), json!(true));
+            let _ = api.update_transaction(&client, &transaction_id, args).await;
+        });
+    }
+}
+
+impl Databases {
+    /// Begin transaction
+    ///
+    /// Create a transaction and return its handle, see
+    /// [`transaction`](crate::transaction).
+    ///* ttl => number?
+    pub async fn begin_transaction(
+        client: &Client,
+        args: HashMap<String, Value>,
+    ) -> Result<TransactionHandle, Error> {
+        let transaction = Self::create_transaction(client, args).await?;
+        Ok(TransactionHandle::new(Api::Databases, client, transaction))
+    }
+}
+
+impl TablesDb {
+    /// Begin transaction
+    ///
+    /// Create a transaction and return its handle, see
+    /// [`transaction`](crate::transaction).
+    ///* ttl => number?
+    pub async fn begin_transaction(
+        client: &Client,
+        args: HashMap<String, Value>,
+    ) -> Result<TransactionHandle, Error> {
+        let transaction = Self::create_transaction(client, args).await?;
+        Ok(TransactionHandle::new(Api::TablesDb, client, transaction))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::transport::{MockResponse, MockTransport};
+
+    fn transaction(status: &str) -> MockResponse {
+        MockResponse::json(
+            200,
+            json!({"$id": "tx1", "$createdAt": "", "$updatedAt": "", "status": status,
+                   "operations": 0, "expiresAt": ""}),
+        )
+    }
+
+    fn document(id: &str) -> MockResponse {
+        MockResponse::json(
+            201,
+            json!({"$id": id, "$collectionId": "orders", "$databaseId": "shop",
+                   "$createdAt": "", "$updatedAt": "", "$permissions": []}),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_transaction_tags_calls_and_commits() -> Result<(), Error> {
+        let (mock, client) = MockTransport::client()?;
+        let documents = "/databases/shop/collections/orders/documents";
+        mock.on(
+            HttpMethod::POST,
+            "/databases/transactions",
+            transaction("pending"),
+        )
+        .on(HttpMethod::POST, documents, document("order-1"))
+        .on(
+            HttpMethod::GET,
+            &format!("{}/order-1", documents),
+            document("order-1"),
+        )
+        .on(
+            HttpMethod::DELETE,
+            &format!("{}/order-0", documents),
+            MockResponse::bytes(204, ""),
+        )
+        .on(
+            HttpMethod::PATCH,
+            "/databases/transactions/tx1",
+            transaction("committed"),
+        );
+
+        let tx = Databases::begin_transaction(&client, HashMap::new()).await?;
+        let mut args = HashMap::new();
+        args.insert("documentId".to_string(), json!("order-1"));
+        args.insert("data".to_string(), json!({"total": 30}));
+        Databases::create_documents(tx.client(), "shop", "orders", args).await?;
+        Databases::get_document(tx.client(), "shop", "orders", "order-1", HashMap::new()).await?;
+        Databases::delete_document(tx.client(), "shop", "orders", "order-0").await?;
+        let committed = tx.commit().await?;
+        assert_eq!(committed.status, "committed");
+
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 5);
+        assert_eq!(requests[0].body.as_ref().unwrap(), &json!({}));
+        assert_eq!(
+            requests[1].body.as_ref().unwrap()["transactionId"],
+            json!("tx1")
+        );
+        assert!(requests[2]
+            .query
+            .contains(&("transactionId".to_string(), "tx1".to_string())));
+        assert_eq!(
+            requests[3].body.as_ref().unwrap()["transactionId"],
+            json!("tx1")
+        );
+        assert_eq!(requests[4].body.as_ref().unwrap(), &json!({"commit": true}));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_transaction_rolls_back_on_drop() -> Result<(), Error> {
+        let (mock, client) = MockTransport::client()?;
+        mock.on(
+            HttpMethod::POST,
+            "/tablesdb/transactions",
+            transaction("pending"),
+        )
+        .on(
+            HttpMethod::PATCH,
+            "/tablesdb/transactions/tx1",
+            transaction("rolledBack"),
+        );
+
+        let tx = TablesDb::begin_transaction(&client, HashMap::new()).await?;
+        drop(tx);
+        for _ in 0..100 {
+            if mock.requests().len() == 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].path, "/tablesdb/transactions/tx1");
+        assert_eq!(
+            requests[1].body.as_ref().unwrap(),
+            &json!({"rollback": true})
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,389 @@
+//! # Transport
+//!
+//! Every service goes through [`Client::call`](crate::client::Client::call), which
+//! hands the prepared request to an [`HttpTransport`]. The default transport is
+//! [`ReqwestTransport`]; [`MockTransport`] answers from canned responses so code
+//! built on the services can be tested without a running Appwrite instance.
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    sync::{Arc, Mutex},
+};
+
+use futures_util::future::BoxFuture;
+use reqwest::{
+    header::{HeaderMap, HeaderValue, CONTENT_TYPE},
+    multipart::Form,
+    Response,
+};
+use serde_json::{json, Value};
+
+use crate::{
+    client::{Client, ClientBuilder},
+    enumm::HttpMethod,
+    error::Error,
+    pinning,
+};
+
+/// Body of an outgoing request.
+#[derive(Debug)]
+pub enum RequestBody {
+    /// No body, used for `GET` requests.
+    Empty,
+    /// JSON encoded params.
+    Json(Value),
+    /// Multipart form used by file and deployment uploads.
+    Multipart(Form),
+}
+
+/// Request prepared by the [`Client`](crate::client::Client).
+#[derive(Debug)]
+pub struct TransportRequest {
+    pub method: HttpMethod,
+    /// Full url including the endpoint and the query string.
+    pub url: String,
+    /// Api path relative to the endpoint, e.g. `/databases`.
+    pub path: String,
+    /// Call headers merged with the client headers, client headers win.
+    pub headers: HeaderMap,
+    pub body: RequestBody,
+}
+
+/// Sends requests on behalf of a [`Client`](crate::client::Client).
+pub trait HttpTransport: Debug + Send + Sync {
+    fn send(&self, request: TransportRequest) -> BoxFuture<'_, Result<Response, Error>>;
+}
+
+/// Default transport backed by a pooled [`reqwest::Client`].
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+    http: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    pub fn new(http: reqwest::Client) -> Self {
+        Self { http }
+    }
+}
+
+impl HttpTransport for ReqwestTransport {
+    fn send(&self, request: TransportRequest) -> BoxFuture<'_, Result<Response, Error>> {
+        Box::pin(async move {
+            let res = match request.method {
+                HttpMethod::GET => self.http.get(&request.url),
+                HttpMethod::POST => self.http.post(&request.url),
+                HttpMethod::PUT => self.http.put(&request.url),
+                HttpMethod::DELETE => self.http.delete(&request.url),
+                HttpMethod::PATCH => self.http.patch(&request.url),
+            };
+            let res = match request.body {
+                RequestBody::Empty => res,
+                RequestBody::Json(params) => res.json(&params),
+                RequestBody::Multipart(form) => res.multipart(form),
+            };
+            res.headers(request.headers)
+                .send()
+                .await
+                .map_err(pinning::network_error)
+        })
+    }
+}
+
+/// Canned response returned by a [`MockTransport`].
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    pub status: u16,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+impl MockResponse {
+    /// Response with a JSON body.
+    pub fn json(status: u16, body: Value) -> Self {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        Self {
+            status,
+            headers,
+            body: body.to_string().into_bytes(),
+        }
+    }
+
+    /// Response with a raw body, e.g. a file download.
+    pub fn bytes(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            headers: HeaderMap::new(),
+            body: body.into(),
+        }
+    }
+
+    pub fn with_header(mut self, key: &'static str, value: &str) -> Result<Self, Error> {
+        self.headers.insert(key, HeaderValue::from_str(value)?);
+        Ok(self)
+    }
+
+    /// Convert into a [`Response`], e.g. to answer from an interceptor.
+    pub fn into_response(self) -> Result<Response, Error> {
+        let mut builder = http::Response::builder().status(self.status);
+        if let Some(headers) = builder.headers_mut() {
+            headers.extend(self.headers);
+        }
+        builder
+            .body(self.body)
+            .map(Response::from)
+            .map_err(|err| Error::Custom(format!("invalid mock response: {}", err)))
+    }
+}
+
+/// Request seen by a [`MockTransport`].
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: HttpMethod,
+    pub path: String,
+    /// Decoded query string pairs, in order.
+    pub query: Vec<(String, String)>,
+    pub headers: HeaderMap,
+    /// JSON body, `None` for `GET` and multipart requests.
+    pub body: Option<Value>,
+    pub multipart: bool,
+}
+
+impl RecordedRequest {
+    /// First query value for `key`.
+    pub fn query_value(&self, key: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn header(&self, key: &str) -> Option<&str> {
+        self.headers.get(key).and_then(|v| v.to_str().ok())
+    }
+}
+
+/// In-memory transport answering from responses registered per method and path.
+///
+/// Responses registered for the same route are returned in order and the last
+/// one is repeated. Unregistered routes answer with a `404`.
+/// ```
+/// use std::sync::Arc;
+/// use serde_json::json;
+/// use unofficial_appwrite::{
+///     client::ClientBuilder,
+///     enumm::HttpMethod,
+///     transport::{MockResponse, MockTransport},
+/// };
+///
+/// let mock = Arc::new(MockTransport::new());
+/// mock.on(HttpMethod::GET, "/health", MockResponse::json(200, json!({"status": "pass"})));
+/// let client = ClientBuilder::default()
+///     .set_transport(mock.clone())
+///     .unwrap()
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Default)]
+pub struct MockTransport {
+    routes: Mutex<HashMap<(HttpMethod, String), Vec<MockResponse>>>,
+    requests: Mutex<Vec<RecordedRequest>>,
+}
+
+impl MockTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A new mock and a default [`Client`] sending through it.
+    pub fn client() -> Result<(Arc<Self>, Client), Error> {
+        let mock = Arc::new(Self::new());
+        let client = ClientBuilder::default()
+            .set_transport(mock.clone())?
+            .build()?;
+        Ok((mock, client))
+    }
+
+    /// Register a response for `method` and api `path`.
+    pub fn on(&self, method: HttpMethod, path: &str, response: MockResponse) -> &Self {
+        self.routes
+            .lock()
+            .expect("mock routes poisoned")
+            .entry((method, path.to_string()))
+            .or_default()
+            .push(response);
+        self
+    }
+
+    /// All requests sent so far, oldest first.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests
+            .lock()
+            .expect("mock requests poisoned")
+            .clone()
+    }
+
+    pub fn last_request(&self) -> Option<RecordedRequest> {
+        self.requests
+            .lock()
+            .expect("mock requests poisoned")
+            .last()
+            .cloned()
+    }
+
+    fn respond(&self, method: HttpMethod, path: &str) -> MockResponse {
+        let mut routes = self.routes.lock().expect("mock routes poisoned");
+        match routes.get_mut(&(method, path.to_string())) {
+            Some(queue) if queue.len() > 1 => queue.remove(0),
+            Some(queue) if !queue.is_empty() => queue[0].clone(),
+            _ => MockResponse::json(
+                404,
+                json!({
+                    "message": format!("no mock response registered for {:?} {}", method, path),
+                    "code": 404,
+                    "type": "general_route_not_found",
+                }),
+            ),
+        }
+    }
+}
+
+impl HttpTransport for MockTransport {
+    fn send(&self, request: TransportRequest) -> BoxFuture<'_, Result<Response, Error>> {
+        Box::pin(async move {
+            let query = url::Url::parse(&request.url)
+                .map(|url| url.query_pairs().into_owned().collect())
+                .unwrap_or_default();
+            let (body, multipart) = match request.body {
+                RequestBody::Empty => (None, false),
+                RequestBody::Json(params) => (Some(params), false),
+                RequestBody::Multipart(_) => (None, true),
+            };
+            self.requests
+                .lock()
+                .expect("mock requests poisoned")
+                .push(RecordedRequest {
+                    method: request.method,
+                    path: request.path.clone(),
+                    query,
+                    headers: request.headers,
+                    body,
+                    multipart,
+                });
+            self.respond(request.method, &request.path).into_response()
+        })
+    }
+}
+
+impl<T: HttpTransport + ?Sized> HttpTransport for Arc<T> {
+    fn send(&self, request: TransportRequest) -> BoxFuture<'_, Result<Response, Error>> {
+        (**self).send(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        client::ClientBuilder, retry::RetryPolicy, services::server::databases::Databases,
+        services::server::users::Users,
+    };
+
+    #[tokio::test]
+    async fn test_mock_transport() -> Result<(), Error> {
+        let mock = Arc::new(MockTransport::new());
+        mock.on(
+            HttpMethod::GET,
+            "/databases/db/collections/col/documents",
+            MockResponse::json(
+                200,
+                json!({"total": 1, "documents": [{
+                    "$id": "doc", "$collectionId": "col", "$databaseId": "db",
+                    "$createdAt": "", "$updatedAt": "", "$permissions": [], "title": "bamboo"
+                }]}),
+            ),
+        );
+        let client = ClientBuilder::default()
+            .set_project("project")?
+            .set_transport(mock.clone())?
+            .build()?;
+
+        let list = Databases::list_documents(
+            &client,
+            "db",
+            "col",
+            maplit::hashmap! {"queries".into() => json!([r#"{"method":"limit","values":[1]}"#])},
+        )
+        .await?;
+        assert_eq!(list.documents[0].data["title"], "bamboo");
+
+        let request = mock.last_request().expect("request recorded");
+        assert_eq!(request.header("x-appwrite-project"), Some("project"));
+        assert_eq!(
+            request.query_value("queries[]"),
+            Some(r#"{"method":"limit","values":[1]}"#)
+        );
+
+        let err = Users::get(&client, "missing").await.unwrap_err();
+        assert!(matches!(
+            err,
+            Error::AppWriteError {
+                code: Some(404),
+                ..
+            }
+        ));
+
+        mock.on(
+            HttpMethod::GET,
+            "/users/proxy",
+            MockResponse::bytes(502, "<html>Bad Gateway</html>")
+                .with_header("content-type", "text/html")?,
+        );
+        let err = Users::get(&client, "proxy").await.unwrap_err();
+        assert!(matches!(
+            &err,
+            Error::UnexpectedResponse { status: 502, content_type: Some(content_type), path, body, .. }
+                if content_type == "text/html" && path == "/users/proxy" && body.contains("Bad Gateway")
+        ));
+        assert!(err.is_server_error());
+
+        mock.on(
+            HttpMethod::GET,
+            "/users/moved",
+            MockResponse::bytes(302, ""),
+        );
+        let err = Users::get(&client, "moved").await.unwrap_err();
+        assert!(matches!(err, Error::UnexpectedStatus { status: 302, .. }));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_retry_with_mock_transport() -> Result<(), Error> {
+        let mock = Arc::new(MockTransport::new());
+        mock.on(
+            HttpMethod::DELETE,
+            "/users/user",
+            MockResponse::json(503, json!({"message": "busy", "code": 503})),
+        )
+        .on(
+            HttpMethod::DELETE,
+            "/users/user",
+            MockResponse::bytes(204, ""),
+        );
+        let client = ClientBuilder::default()
+            .set_transport(mock.clone())?
+            .set_retry_policy(RetryPolicy {
+                base_delay: Duration::from_millis(1),
+                ..Default::default()
+            })?
+            .build()?;
+
+        Users::delete(&client, "user").await?;
+        assert_eq!(mock.requests().len(), 2);
+        Ok(())
+    }
+}
@@ -1,4 +1,6 @@
-use crate::client::Client;
+use serde_json::Value;
+
+use crate::{client::Client, error::Error};
 
 extern crate reqwest;
 extern crate serde_json;
@@ -33,3 +35,119 @@ pub fn get_content_header_value<'a>(client: &'a Client, value: &'a str) -> Optio
         .get(format!("x-appwrite-{value}"))
         .and_then(|g| g.to_str().ok())
 }
+
+/// Flatten GET params into `(key, value)` pairs.
+///
+/// Arrays become `key[]` pairs, except arrays holding arrays or objects which are
+/// indexed (`key[0][]`) so they stay unambiguous. Objects become `key[field]`
+/// pairs and `null` values are skipped.
+pub fn flatten_params(params: &Value) -> Result<Vec<(String, String)>, Error> {
+    let object = params.as_object().ok_or(Error::Custom(
+        "Unable to convert value because it's not an object".to_string(),
+    ))?;
+    let mut pairs = Vec::new();
+    for (key, value) in object {
+        flatten_value(key.clone(), value, &mut pairs);
+    }
+    Ok(pairs)
+}
+
+fn flatten_value(key: String, value: &Value, pairs: &mut Vec<(String, String)>) {
+    match value {
+        Value::Null => {}
+        Value::Bool(v) => pairs.push((key, v.to_string())),
+        Value::Number(v) => pairs.push((key, v.to_string())),
+        Value::String(v) => pairs.push((key, v.clone())),
+        Value::Array(values) => {
+            let nested = values.iter().any(|v| v.is_array() || v.is_object());
+            for (i, v) in values.iter().enumerate() {
+                let key = match nested {
+                    true => format!("{}[{}]", key, i),
+                    false => format!("{}[]", key),
+                };
+                flatten_value(key, v, pairs);
+            }
+        }
+        Value::Object(fields) => {
+            for (field, v) in fields {
+                flatten_value(format!("{}[{}]", key, field), v, pairs);
+            }
+        }
+    }
+}
+
+/// Percent-encode GET params into a query string, including the leading `?`.
+///
+/// Returns an empty string when there is nothing to send.
+pub fn encode_query(params: &Value) -> Result<String, Error> {
+    let pairs = flatten_params(params)?;
+    if pairs.is_empty() {
+        return Ok(String::new());
+    }
+    let query = url::form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(pairs)
+        .finish();
+    Ok(format!("?{}", query))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn round_trip(params: Value) -> Vec<(String, String)> {
+        let query = encode_query(&params).unwrap();
+        url::form_urlencoded::parse(query.trim_start_matches('?').as_bytes())
+            .into_owned()
+            .collect()
+    }
+
+    #[test]
+    fn test_encode_query() {
+        let params = json!({
+            "search": "tom & jerry = 100%",
+            "threshold": 5000,
+            "width": 120.5,
+            "enabled": true,
+            "missing": null,
+            "queries": [r#"{"method":"equal","attribute":"title","values":["a&b"]}"#],
+        });
+        assert_eq!(round_trip(params.clone()), flatten_params(&params).unwrap());
+        assert_eq!(
+            round_trip(params),
+            vec![
+                ("enabled".to_string(), "true".to_string()),
+                (
+                    "queries[]".to_string(),
+                    r#"{"method":"equal","attribute":"title","values":["a&b"]}"#.to_string()
+                ),
+                ("search".to_string(), "tom & jerry = 100%".to_string()),
+                ("threshold".to_string(), "5000".to_string()),
+                ("width".to_string(), "120.5".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_encode_nested_query() {
+        let params = json!({
+            "matrix": [[1, 2], [3]],
+            "filter": {"name": "a b", "tags": ["x", null, "y"]},
+        });
+        assert_eq!(
+            round_trip(params),
+            vec![
+                ("filter[name]".to_string(), "a b".to_string()),
+                ("filter[tags][]".to_string(), "x".to_string()),
+                ("filter[tags][]".to_string(), "y".to_string()),
+                ("matrix[0][]".to_string(), "1".to_string()),
+                ("matrix[0][]".to_string(), "2".to_string()),
+                ("matrix[1][]".to_string(), "3".to_string()),
+            ]
+        );
+        assert_eq!(encode_query(&json!({})).unwrap(), "");
+        assert_eq!(encode_query(&json!({"gone": null})).unwrap(), "");
+        assert!(encode_query(&json!(["not", "an", "object"])).is_err());
+    }
+}
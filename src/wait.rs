@@ -0,0 +1,182 @@
+//! # Wait
+//!
+//! Appwrite creates attributes and indexes in the background: they start as
+//! `processing` and become `available`, or `failed` or `stuck`. The
+//! `wait_for_*` helpers of
+//! [`Databases`](crate::services::server::databases::Databases) poll until
+//! they are usable.
+use std::{future::Future, time::Duration};
+
+use tokio::time::Instant;
+
+use crate::error::Error;
+
+/// Polling settings of the `wait_for_*` helpers.
+#[derive(Debug, Clone)]
+pub struct WaitOptions {
+    /// Delay between two polls.
+    pub interval: Duration,
+    /// Give up with [`Error::WaitTimeout`] after this long.
+    pub timeout: Duration,
+}
+
+impl Default for WaitOptions {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_millis(500),
+            timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Outcome of one poll.
+pub(crate) enum Poll<T> {
+    Ready(T),
+    /// Not ready yet, with the status reported in a timeout.
+    Pending(String),
+}
+
+/// Map the `status` of `resource` to a poll, failing on `failed` and `stuck`
+/// with the server's `error` message.
+pub(crate) fn check_status(resource: &str, status: &str, error: &str) -> Result<Poll<()>, Error> {
+    match status {
+        "available" => Ok(Poll::Ready(())),
+        "failed" | "stuck" => Err(Error::ResourceFailed {
+            resource: resource.to_string(),
+            status: status.to_string(),
+            message: error.to_string(),
+        }),
+        status => Ok(Poll::Pending(status.to_string())),
+    }
+}
+
+/// Call `check` every `options.interval` until it is ready.
+pub(crate) async fn poll<T, F, Fut>(
+    resource: &str,
+    options: &WaitOptions,
+    mut check: F,
+) -> Result<T, Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<Poll<T>, Error>>,
+{
+    let deadline = Instant::now() + options.timeout;
+    loop {
+        let status = match check().await? {
+            Poll::Ready(value) => return Ok(value),
+            Poll::Pending(status) => status,
+        };
+        let now = Instant::now();
+        if now >= deadline {
+            return Err(Error::WaitTimeout {
+                resource: resource.to_string(),
+                status,
+                timeout: options.timeout,
+            });
+        }
+        tokio::time::sleep(options.interval.min(deadline - now)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        enumm::HttpMethod,
+        services::server::databases::Databases,
+        transport::{MockResponse, MockTransport},
+    };
+
+    fn index(status: &str, error: &str) -> MockResponse {
+        MockResponse::json(
+            200,
+            json!({"key": "title_search", "type": "fulltext", "status": status, "error": error,
+                   "attributes": ["title"], "orders": []}),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_index() -> Result<(), Error> {
+        let (mock, client) = MockTransport::client()?;
+        let options = WaitOptions {
+            interval: Duration::from_millis(1),
+            timeout: Duration::from_millis(200),
+        };
+        let path = "/databases/db/collections/books/indexes/title_search";
+
+        mock.on(HttpMethod::GET, path, index("processing", ""))
+            .on(HttpMethod::GET, path, index("processing", ""))
+            .on(HttpMethod::GET, path, index("available", ""));
+        let ready =
+            Databases::wait_for_index(&client, "db", "books", "title_search", &options).await?;
+        assert_eq!(ready.status, "available");
+        assert_eq!(mock.requests().len(), 3);
+
+        let (mock, client) = MockTransport::client()?;
+        mock.on(
+            HttpMethod::GET,
+            path,
+            index("failed", "Attribute not found: title"),
+        );
+        let err = Databases::wait_for_index(&client, "db", "books", "title_search", &options)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "index `books.title_search` is failed: Attribute not found: title"
+        );
+
+        let (mock, client) = MockTransport::client()?;
+        mock.on(HttpMethod::GET, path, index("processing", ""));
+        let err = Databases::wait_for_index(&client, "db", "books", "title_search", &options)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::WaitTimeout { status, .. } if status == "processing"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_collection_ready() -> Result<(), Error> {
+        let (mock, client) = MockTransport::client()?;
+        let collection = |status: &str, error: &str| {
+            MockResponse::json(
+                200,
+                json!({"$id": "books", "$createdAt": "", "$updatedAt": "", "$permissions": [],
+                       "databaseId": "db", "name": "Books", "enabled": true, "documentSecurity": false,
+                       "attributes": [
+                           {"key": "title", "type": "string", "status": "available", "error": "",
+                            "required": true, "size": 255},
+                           {"key": "rating", "type": "integer", "status": status, "error": error,
+                            "required": false},
+                       ],
+                       "indexes": []}),
+            )
+        };
+        let path = "/databases/db/collections/books";
+        mock.on(HttpMethod::GET, path, collection("processing", ""))
+            .on(
+                HttpMethod::GET,
+                path,
+                collection("stuck", "Database timeout"),
+            );
+        let err = Databases::wait_for_collection_ready(
+            &client,
+            "db",
+            "books",
+            &WaitOptions {
+                interval: Duration::from_millis(1),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "attribute `books.rating` is stuck: Database timeout"
+        );
+        assert_eq!(mock.requests().len(), 2);
+        Ok(())
+    }
+}
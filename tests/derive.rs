@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use unofficial_appwrite::{
+    enumm::HttpMethod,
+    enums::{index_type::IndexType, relation_mutate::RelationMutate},
+    error::Error,
+    schema::{AppwriteCollection as _, AttributeKind},
+    services::server::databases::Databases,
+    transport::{MockResponse, MockTransport},
+};
+use unofficial_appwrite_derive::AppwriteCollection;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, AppwriteCollection)]
+#[appwrite(collection = "books", name = "Books")]
+#[appwrite(index(key = "title_search", kind = "fulltext", attributes("title")))]
+struct Book {
+    #[appwrite(size = 255)]
+    title: String,
+    #[appwrite(email)]
+    contact: Option<String>,
+    #[appwrite(min = 0, max = 5, default = 3)]
+    rating: Option<i64>,
+    #[appwrite(elements("draft", "published"), indexed)]
+    status: String,
+    #[serde(rename = "publishedAt")]
+    #[appwrite(datetime)]
+    published_at: Option<String>,
+    #[appwrite(size = 32)]
+    tags: Vec<String>,
+    #[appwrite(relationship(related = "authors", kind = "manyToOne", on_delete = "setNull"))]
+    author: Option<Value>,
+    #[serde(skip)]
+    cached: bool,
+    #[serde(skip_serializing, default)]
+    #[appwrite(size = 64)]
+    draft_note: String,
+}
+
+#[test]
+fn test_derived_schema() {
+    let schema = Book::schema();
+    assert_eq!(Book::COLLECTION_ID, "books");
+    assert_eq!(schema.name, "Books");
+
+    let keys: Vec<_> = schema.attributes.iter().map(|a| a.key.as_str()).collect();
+    assert_eq!(
+        keys,
+        [
+            "title",
+            "contact",
+            "rating",
+            "status",
+            "publishedAt",
+            "tags",
+            "author"
+        ]
+    );
+    let title = &schema.attributes[0];
+    assert!(title.required && !title.array);
+    assert_eq!(
+        title.kind,
+        AttributeKind::String {
+            size: 255,
+            encrypt: false
+        }
+    );
+    let rating = &schema.attributes[2];
+    assert!(!rating.required);
+    assert_eq!(rating.default, Some(json!(3)));
+    assert_eq!(rating.to_args()["max"], json!(5));
+    assert_eq!(schema.attributes[4].kind, AttributeKind::Datetime);
+    assert!(schema.attributes[5].array);
+    assert!(matches!(
+        &schema.attributes[6].kind,
+        AttributeKind::Relationship { related_collection, on_delete: RelationMutate::SetNull, .. }
+            if related_collection == "authors"
+    ));
+
+    assert_eq!(schema.indexes.len(), 2);
+    assert_eq!(schema.indexes[0].index_type, IndexType::Fulltext);
+    assert_eq!(schema.indexes[1].key, "key_status");
+    assert_eq!(schema.indexes[1].attributes, ["status"]);
+}
+
+#[derive(Debug, Serialize, Deserialize, AppwriteCollection)]
+#[serde(rename_all = "camelCase")]
+#[appwrite(collection = "reviews")]
+struct Review {
+    #[appwrite(datetime)]
+    created_at: String,
+    #[serde(rename = "stars")]
+    star_count: i64,
+    #[appwrite(key = "text", size = 1024)]
+    review_body: Option<String>,
+}
+
+#[test]
+fn test_derived_rename_all() {
+    let review = Review {
+        created_at: String::new(),
+        star_count: 4,
+        review_body: None,
+    };
+    let payload = serde_json::to_value(&review).unwrap();
+    let keys: Vec<_> = Review::schema()
+        .attributes
+        .into_iter()
+        .map(|a| a.key)
+        .collect();
+    assert_eq!(keys, ["createdAt", "stars", "text"]);
+    assert!(payload.get("createdAt").is_some() && payload.get("stars").is_some());
+}
+
+#[tokio::test]
+async fn test_derived_crud() -> Result<(), Error> {
+    let (mock, client) = MockTransport::client()?;
+
+    mock.on(
+        HttpMethod::GET,
+        "/databases/db/collections/books/documents/dune",
+        MockResponse::json(
+            200,
+            json!({
+                "$id": "dune", "$collectionId": "books", "$databaseId": "db",
+                "$createdAt": "", "$updatedAt": "", "$permissions": [],
+                "title": "Dune", "contact": null, "rating": 5, "status": "published",
+                "publishedAt": null, "tags": ["sf"], "author": null,
+            }),
+        ),
+    );
+    let book = Databases::get_document_of::<Book>(&client, "db", "dune", HashMap::new()).await?;
+    assert_eq!(book.data.title, "Dune");
+    assert_eq!(book.data.rating, Some(5));
+    Ok(())
+}
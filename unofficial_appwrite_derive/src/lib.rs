@@ -0,0 +1,570 @@
+//! # Unofficial Appwrite derive
+//!
+//! `#[derive(AppwriteCollection)]` maps a struct to the schema of an Appwrite
+//! collection. Use it through the `derive` feature of `unofficial_appwrite`.
+//!
+//! Struct attributes:
+//! * `collection = "id"` => collection id, required
+//! * `name = "Name"` => collection name, defaults to the struct name
+//! * `document_security` => enable document level permissions
+//! * `index(key = "..", kind = "key" | "unique" | "fulltext", attributes(..), orders(..))`
+//!
+//! Field attributes:
+//! * `key = ".."` => attribute key, defaults to the serde name of the field,
+//!   following `#[serde(rename = "..")]` and the struct's `#[serde(rename_all = "..")]`
+//! * `size = 255` and `encrypt` => string attributes
+//! * `min = ..`, `max = ..` => integer and float attributes
+//! * `email`, `url`, `ip`, `datetime` => string formats
+//! * `elements("a", "b")` => enum attribute
+//! * `relationship(related = "..", kind = "oneToOne" | .., two_way, two_way_key = "..", on_delete = "cascade" | "restrict" | "setNull")`
+//! * `default = <literal>`
+//! * `required` => override the requiredness inferred from `Option`
+//! * `indexed`, `unique`, `fulltext` => single attribute index
+//! * `skip` => not an attribute, also implied by `#[serde(skip)]` and
+//!   `#[serde(skip_serializing)]`
+//!
+//! The attribute type is inferred from the field type when no format is
+//! given: strings, integers, floats, `bool` and chrono dates. `Option<T>`
+//! makes the attribute optional and `Vec<T>` makes it an array.
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{
+    meta::ParseNestedMeta, parse_macro_input, Data, DeriveInput, Error, Expr, Fields,
+    GenericArgument, LitBool, LitStr, PathArguments, Result, Type,
+};
+
+#[proc_macro_derive(AppwriteCollection, attributes(appwrite))]
+pub fn derive_appwrite_collection(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct Index {
+    key: Option<String>,
+    kind: Option<String>,
+    attributes: Vec<String>,
+    orders: Vec<String>,
+}
+
+#[derive(Default)]
+struct Relationship {
+    related: Option<String>,
+    kind: Option<String>,
+    two_way: bool,
+    two_way_key: Option<String>,
+    on_delete: Option<String>,
+}
+
+#[derive(Default)]
+struct FieldAttrs {
+    key: Option<String>,
+    size: Option<Expr>,
+    encrypt: bool,
+    min: Option<Expr>,
+    max: Option<Expr>,
+    format: Option<&'static str>,
+    elements: Vec<String>,
+    relationship: Option<Relationship>,
+    default: Option<Expr>,
+    required: Option<bool>,
+    index: Option<&'static str>,
+    skip: bool,
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2> {
+    let ident = &input.ident;
+    let mut collection = None;
+    let mut name = None;
+    let mut document_security = false;
+    let mut indexes = Vec::new();
+
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("appwrite")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("collection") {
+                collection = Some(string(&meta)?);
+            } else if meta.path.is_ident("name") {
+                name = Some(string(&meta)?);
+            } else if meta.path.is_ident("document_security") {
+                document_security = true;
+            } else if meta.path.is_ident("index") {
+                let mut index = Index::default();
+                meta.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("key") {
+                        index.key = Some(string(&meta)?);
+                    } else if meta.path.is_ident("kind") {
+                        index.kind = Some(string(&meta)?);
+                    } else if meta.path.is_ident("attributes") {
+                        index.attributes = strings(&meta)?;
+                    } else if meta.path.is_ident("orders") {
+                        index.orders = strings(&meta)?;
+                    } else {
+                        return Err(meta.error("unknown index setting"));
+                    }
+                    Ok(())
+                })?;
+                indexes.push(index);
+            } else {
+                return Err(meta.error("unknown appwrite collection setting"));
+            }
+            Ok(())
+        })?;
+    }
+    let collection = collection.ok_or_else(|| {
+        Error::new_spanned(
+            ident,
+            "missing #[appwrite(collection = \"...\")] on the struct",
+        )
+    })?;
+    let name = name.unwrap_or_else(|| ident.to_string());
+    let rename_all = serde_rename_all(&input)?;
+
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            ident,
+            "AppwriteCollection can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new_spanned(
+            ident,
+            "AppwriteCollection needs a struct with named fields",
+        ));
+    };
+
+    let mut attributes = Vec::new();
+    for field in &fields.named {
+        let attrs = field_attrs(field)?;
+        let serde = serde_field(field)?;
+        if attrs.skip || serde.skip {
+            continue;
+        }
+        let field_ident = field.ident.as_ref().expect("named field");
+        let key = match attrs.key.clone().or(serde.rename) {
+            Some(key) => key,
+            None => {
+                let name = field_ident.to_string().trim_start_matches("r#").to_string();
+                match &rename_all {
+                    Some(rule) => rename_field(&name, rule),
+                    None => name,
+                }
+            }
+        };
+        if let Some(kind) = attrs.index {
+            indexes.push(Index {
+                key: Some(format!("{}_{}", kind, key)),
+                kind: Some(kind.to_string()),
+                attributes: vec![key.clone()],
+                orders: Vec::new(),
+            });
+        }
+        attributes.push(attribute(field_ident, &field.ty, &key, attrs)?);
+    }
+
+    let indexes = indexes
+        .into_iter()
+        .map(|index| index_tokens(ident, index))
+        .collect::<Result<Vec<_>>>()?;
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::unofficial_appwrite::schema::AppwriteCollection for #ident #ty_generics #where_clause {
+            const COLLECTION_ID: &'static str = #collection;
+
+            fn schema() -> ::unofficial_appwrite::schema::CollectionSchema {
+                ::unofficial_appwrite::schema::CollectionSchema {
+                    id: #collection.to_string(),
+                    name: #name.to_string(),
+                    document_security: #document_security,
+                    attributes: vec![#(#attributes),*],
+                    indexes: vec![#(#indexes),*],
+                }
+            }
+        }
+    })
+}
+
+fn field_attrs(field: &syn::Field) -> Result<FieldAttrs> {
+    let mut attrs = FieldAttrs::default();
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("appwrite")) {
+        attr.parse_nested_meta(|meta| {
+            let path = &meta.path;
+            let format = ["email", "url", "ip", "datetime"]
+                .into_iter()
+                .find(|format| path.is_ident(format));
+            let index = ["indexed", "unique", "fulltext"]
+                .into_iter()
+                .find(|index| path.is_ident(index));
+            if let Some(format) = format {
+                attrs.format = Some(format);
+            } else if let Some(index) = index {
+                attrs.index = Some(if index == "indexed" { "key" } else { index });
+            } else if path.is_ident("key") {
+                attrs.key = Some(string(&meta)?);
+            } else if path.is_ident("size") {
+                attrs.size = Some(meta.value()?.parse()?);
+            } else if path.is_ident("encrypt") {
+                attrs.encrypt = true;
+            } else if path.is_ident("min") {
+                attrs.min = Some(meta.value()?.parse()?);
+            } else if path.is_ident("max") {
+                attrs.max = Some(meta.value()?.parse()?);
+            } else if path.is_ident("elements") {
+                attrs.elements = strings(&meta)?;
+            } else if path.is_ident("default") {
+                attrs.default = Some(meta.value()?.parse()?);
+            } else if path.is_ident("required") {
+                attrs.required = Some(match meta.value() {
+                    Ok(value) => value.parse::<LitBool>()?.value,
+                    Err(_) => true,
+                });
+            } else if path.is_ident("skip") {
+                attrs.skip = true;
+            } else if path.is_ident("relationship") {
+                let mut relationship = Relationship::default();
+                meta.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("related") {
+                        relationship.related = Some(string(&meta)?);
+                    } else if meta.path.is_ident("kind") {
+                        relationship.kind = Some(string(&meta)?);
+                    } else if meta.path.is_ident("two_way") {
+                        relationship.two_way = true;
+                    } else if meta.path.is_ident("two_way_key") {
+                        relationship.two_way_key = Some(string(&meta)?);
+                    } else if meta.path.is_ident("on_delete") {
+                        relationship.on_delete = Some(string(&meta)?);
+                    } else {
+                        return Err(meta.error("unknown relationship setting"));
+                    }
+                    Ok(())
+                })?;
+                attrs.relationship = Some(relationship);
+            } else {
+                return Err(meta.error("unknown appwrite attribute setting"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(attrs)
+}
+
+/// Serde field settings which change the serialized document.
+#[derive(Default)]
+struct SerdeField {
+    /// `#[serde(rename = "..")]`
+    rename: Option<String>,
+    /// `#[serde(skip)]` or `#[serde(skip_serializing)]`
+    skip: bool,
+}
+
+fn serde_field(field: &syn::Field) -> Result<SerdeField> {
+    let mut serde = SerdeField::default();
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("serde")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") && meta.input.peek(syn::Token![=]) {
+                serde.rename = Some(string(&meta)?);
+            } else if meta.path.is_ident("skip") || meta.path.is_ident("skip_serializing") {
+                serde.skip = true;
+            } else if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<Expr>()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                meta.parse_nested_meta(|meta| {
+                    if meta.input.peek(syn::Token![=]) {
+                        meta.value()?.parse::<Expr>()?;
+                    }
+                    Ok(())
+                })?;
+            }
+            Ok(())
+        })?;
+    }
+    Ok(serde)
+}
+
+/// Field rules accepted by `#[serde(rename_all = "..")]`.
+const RENAME_RULES: [&str; 8] = [
+    "lowercase",
+    "UPPERCASE",
+    "PascalCase",
+    "camelCase",
+    "snake_case",
+    "SCREAMING_SNAKE_CASE",
+    "kebab-case",
+    "SCREAMING-KEBAB-CASE",
+];
+
+/// Serialize rule of a `#[serde(rename_all = "..")]` container attribute.
+fn serde_rename_all(input: &DeriveInput) -> Result<Option<String>> {
+    let mut rule = None;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("serde")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename_all") && meta.input.peek(syn::Token![=]) {
+                let lit = meta.value()?.parse::<LitStr>()?;
+                rule = Some(rename_rule(lit)?);
+            } else if meta.path.is_ident("rename_all") && meta.input.peek(syn::token::Paren) {
+                meta.parse_nested_meta(|meta| {
+                    let lit = meta.value()?.parse::<LitStr>()?;
+                    if meta.path.is_ident("serialize") {
+                        rule = Some(rename_rule(lit)?);
+                    }
+                    Ok(())
+                })?;
+            } else if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<Expr>()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                meta.parse_nested_meta(|meta| {
+                    if meta.input.peek(syn::Token![=]) {
+                        meta.value()?.parse::<Expr>()?;
+                    }
+                    Ok(())
+                })?;
+            }
+            Ok(())
+        })?;
+    }
+    Ok(rule)
+}
+
+fn rename_rule(lit: LitStr) -> Result<String> {
+    let rule = lit.value();
+    if RENAME_RULES.contains(&rule.as_str()) {
+        Ok(rule)
+    } else {
+        Err(Error::new_spanned(
+            lit,
+            format!(
+                "unknown rename_all rule `{}`, expected one of {:?}",
+                rule, RENAME_RULES
+            ),
+        ))
+    }
+}
+
+/// Apply a `rename_all` rule to a snake_case field name, as serde does.
+fn rename_field(name: &str, rule: &str) -> String {
+    let pascal = || {
+        name.split('_')
+            .map(|word| {
+                let mut chars = word.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                    None => String::new(),
+                }
+            })
+            .collect::<String>()
+    };
+    match rule {
+        "lowercase" | "snake_case" => name.to_string(),
+        "UPPERCASE" | "SCREAMING_SNAKE_CASE" => name.to_ascii_uppercase(),
+        "PascalCase" => pascal(),
+        "camelCase" => {
+            let pascal = pascal();
+            let mut chars = pascal.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        }
+        "kebab-case" => name.replace('_', "-"),
+        _ => name.replace('_', "-").to_ascii_uppercase(),
+    }
+}
+
+fn string(meta: &ParseNestedMeta) -> Result<String> {
+    Ok(meta.value()?.parse::<LitStr>()?.value())
+}
+
+fn strings(meta: &ParseNestedMeta) -> Result<Vec<String>> {
+    let content;
+    syn::parenthesized!(content in meta.input);
+    let list = content.parse_terminated(|input| input.parse::<LitStr>(), syn::Token![,])?;
+    Ok(list.into_iter().map(|lit| lit.value()).collect())
+}
+
+/// Inner type of a single generic wrapper such as `Option<T>` or `Vec<T>`.
+fn unwrap_type<'a>(ty: &'a Type, wrapper: &str) -> Option<&'a Type> {
+    let Type::Path(path) = ty else { return None };
+    let segment = path.path.segments.last()?;
+    if segment.ident != wrapper {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    }
+}
+
+fn type_name(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(path) => path.path.segments.last().map(|s| s.ident.to_string()),
+        Type::Reference(reference) => type_name(&reference.elem),
+        _ => None,
+    }
+}
+
+fn attribute(field: &syn::Ident, ty: &Type, key: &str, attrs: FieldAttrs) -> Result<TokenStream2> {
+    let (ty, optional) = match unwrap_type(ty, "Option") {
+        Some(inner) => (inner, true),
+        None => (ty, false),
+    };
+    let (ty, array) = match unwrap_type(ty, "Vec") {
+        Some(inner) => (inner, true),
+        None => (ty, false),
+    };
+    let required = attrs.required.unwrap_or(!optional);
+    if required && attrs.default.is_some() {
+        return Err(Error::new_spanned(
+            field,
+            "Appwrite does not accept a default on a required attribute, make the field an \
+             `Option` or add #[appwrite(required = false)]",
+        ));
+    }
+    let schema = quote!(::unofficial_appwrite::schema);
+    let enums = quote!(::unofficial_appwrite::enums);
+    let opt = |expr: &Option<Expr>| match expr {
+        Some(expr) => quote!(Some((#expr) as _)),
+        None => quote!(None),
+    };
+
+    let kind = if let Some(relationship) = attrs.relationship {
+        let related = relationship.related.ok_or_else(|| {
+            Error::new_spanned(field, "relationship needs `related = \"collection_id\"`")
+        })?;
+        let relation_type = variant(
+            field,
+            relationship.kind.as_deref().unwrap_or("oneToOne"),
+            &[
+                ("oneToOne", "OneToOne"),
+                ("oneToMany", "OneToMany"),
+                ("manyToOne", "ManyToOne"),
+                ("manyToMany", "ManyToMany"),
+            ],
+        )?;
+        let on_delete = variant(
+            field,
+            relationship.on_delete.as_deref().unwrap_or("restrict"),
+            &[
+                ("cascade", "Cascade"),
+                ("restrict", "Restrict"),
+                ("setNull", "SetNull"),
+            ],
+        )?;
+        let two_way = relationship.two_way;
+        let two_way_key = match relationship.two_way_key {
+            Some(key) => quote!(Some(#key.to_string())),
+            None => quote!(None),
+        };
+        quote!(#schema::AttributeKind::Relationship {
+            related_collection: #related.to_string(),
+            relation_type: #enums::relationship_type::RelationshipType::#relation_type,
+            two_way: #two_way,
+            two_way_key: #two_way_key,
+            on_delete: #enums::relation_mutate::RelationMutate::#on_delete,
+        })
+    } else if !attrs.elements.is_empty() {
+        let elements = &attrs.elements;
+        quote!(#schema::AttributeKind::Enum { elements: vec![#(#elements.to_string()),*] })
+    } else if let Some(format) = attrs.format {
+        match format {
+            "email" => quote!(#schema::AttributeKind::Email),
+            "url" => quote!(#schema::AttributeKind::Url),
+            "ip" => quote!(#schema::AttributeKind::Ip),
+            _ => quote!(#schema::AttributeKind::Datetime),
+        }
+    } else {
+        match type_name(ty).as_deref() {
+            Some("String" | "str") => {
+                let size = attrs.size.as_ref().ok_or_else(|| {
+                    Error::new_spanned(
+                        field,
+                        "string attributes need a size, e.g. #[appwrite(size = 255)]",
+                    )
+                })?;
+                let encrypt = attrs.encrypt;
+                quote!(#schema::AttributeKind::String { size: (#size) as u64, encrypt: #encrypt })
+            }
+            Some(
+                "i8" | "i16" | "i32" | "i64" | "isize" | "u8" | "u16" | "u32" | "u64" | "usize",
+            ) => {
+                let (min, max) = (opt(&attrs.min), opt(&attrs.max));
+                quote!(#schema::AttributeKind::Integer { min: #min, max: #max })
+            }
+            Some("f32" | "f64") => {
+                let (min, max) = (opt(&attrs.min), opt(&attrs.max));
+                quote!(#schema::AttributeKind::Float { min: #min, max: #max })
+            }
+            Some("bool") => quote!(#schema::AttributeKind::Boolean),
+            Some("DateTime" | "NaiveDateTime" | "NaiveDate") => {
+                quote!(#schema::AttributeKind::Datetime)
+            }
+            _ => {
+                return Err(Error::new_spanned(
+                    ty,
+                    "cannot infer the Appwrite attribute type, add a format such as \
+                     #[appwrite(size = ..)], #[appwrite(email)] or #[appwrite(relationship(..))]",
+                ))
+            }
+        }
+    };
+
+    let default = match attrs.default {
+        Some(default) => quote!(Some(#schema::__private::serde_json::json!(#default))),
+        None => quote!(None),
+    };
+    Ok(quote!(#schema::AttributeSchema {
+        key: #key.to_string(),
+        kind: #kind,
+        required: #required,
+        default: #default,
+        array: #array,
+    }))
+}
+
+fn variant(field: &syn::Ident, value: &str, variants: &[(&str, &str)]) -> Result<syn::Ident> {
+    variants
+        .iter()
+        .find(|(name, _)| *name == value)
+        .map(|(_, variant)| syn::Ident::new(variant, Span::call_site()))
+        .ok_or_else(|| {
+            let names: Vec<_> = variants.iter().map(|(name, _)| *name).collect();
+            Error::new_spanned(
+                field,
+                format!("unknown value `{}`, expected one of {:?}", value, names),
+            )
+        })
+}
+
+fn index_tokens(ident: &syn::Ident, index: Index) -> Result<TokenStream2> {
+    let key = index
+        .key
+        .ok_or_else(|| Error::new_spanned(ident, "index needs `key = \"...\"`"))?;
+    if index.attributes.is_empty() {
+        return Err(Error::new_spanned(
+            ident,
+            format!("index `{}` needs `attributes(...)`", key),
+        ));
+    }
+    let kind = variant(
+        ident,
+        index.kind.as_deref().unwrap_or("key"),
+        &[
+            ("key", "Key"),
+            ("unique", "Unique"),
+            ("fulltext", "Fulltext"),
+        ],
+    )?;
+    let attributes = &index.attributes;
+    let orders = &index.orders;
+    Ok(quote!(::unofficial_appwrite::schema::IndexSchema {
+        key: #key.to_string(),
+        index_type: ::unofficial_appwrite::enums::index_type::IndexType::#kind,
+        attributes: vec![#(#attributes.to_string()),*],
+        orders: vec![#(#orders.to_string()),*],
+    }))
+}